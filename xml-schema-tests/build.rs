@@ -35,6 +35,7 @@ fn main() {
 
         let mut proc = Processor::new(&document);
         proc.process_ast(&document);
+        proc.check_unique_particle_attribution(&in_xml);
 
         let renames = Default::default();
         let mut gen = ParserGenerator::new(vec![proc], &parse_context, renames);
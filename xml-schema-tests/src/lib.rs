@@ -4,4 +4,36 @@ extern crate xml_schema;
 #[macro_use]
 extern crate pretty_assertions;
 
+use xml_schema::support::{ParseXml, ParseContext, DefaultParseContext, ParentContext, InnerStream, Tokenizer};
+
+pub mod binary;
+pub mod cdata;
+pub mod empty;
+pub mod facets;
+pub mod floats;
+pub mod ids;
+pub mod integers;
+pub mod lists;
+pub mod mixed;
+pub mod names;
+pub mod namespaces;
+pub mod notation;
+pub mod occurrences;
+pub mod open_content;
 pub mod po;
+pub mod references;
+pub mod substitution;
+pub mod unions;
+pub mod uri;
+
+/// Parses a whole document as a `T`, with default contexts.
+pub fn parse<'input, T: ParseXml<'input>>(input: &'input str) -> Option<T> {
+    parse_with(input, &mut DefaultParseContext::default(), &Default::default())
+}
+
+/// Parses a whole document as a `T`, with the given contexts.
+pub fn parse_with<'input, T: ParseXml<'input>, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<T> {
+    let tokenizer = Tokenizer::from(input);
+    let mut stream = Box::new(InnerStream::new(tokenizer));
+    T::parse_xml(&mut stream, parse_context, parent_context)
+}
@@ -0,0 +1 @@
+include!(concat!(env!("OUT_DIR"), "/open_content.rs"));
@@ -0,0 +1,48 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::binary::unqualified::Attachment;
+
+#[test]
+fn test_hex_binary() {
+    let input = r#"<attachment checksum="cbf43926"/>"#;
+    let doc: Attachment = parse(input).unwrap();
+    let checksum = doc.attr_checksum.unwrap();
+    assert_eq!((checksum.0).0, vec![0xcb, 0xf4, 0x39, 0x26]);
+    assert_eq!(checksum.0.to_string(), "CBF43926");
+}
+
+#[test]
+fn test_base64_binary() {
+    let input = "<attachment thumbnail=\"SGVs bG8s\nIHdv cmxk\" key=\"AQI=\"/>";
+    let doc: Attachment = parse(input).unwrap();
+    let thumbnail = doc.attr_thumbnail.unwrap();
+    assert_eq!(thumbnail.0, b"Hello, world".to_vec());
+    assert_eq!(thumbnail.to_string(), "SGVsbG8sIHdvcmxk");
+    let key = doc.attr_key.unwrap();
+    assert_eq!((key.0).0, vec![1, 2]);
+    assert_eq!(key.0.to_string(), "AQI=");
+}
+
+#[test]
+fn test_octet_length() {
+    // Six hex digits are only three octets.
+    assert!(parse::<Attachment>(r#"<attachment checksum="cbf439"/>"#).is_none());
+}
+
+#[test]
+fn test_base64_max_length() {
+    assert!(parse::<Attachment>(r#"<attachment key="AQID"/>"#).is_none());
+}
+
+#[test]
+fn test_base64_nonzero_padding_bits() {
+    // The last digit carries bits that do not fit in the single octet.
+    assert!(parse::<Attachment>(r#"<attachment thumbnail="AB=="/>"#).is_none());
+}
+
+#[test]
+fn test_odd_hex_digits() {
+    assert!(parse::<Attachment>(r#"<attachment checksum="cbf4392"/>"#).is_none());
+}
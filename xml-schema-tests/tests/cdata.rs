@@ -0,0 +1,56 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::cdata::unqualified::{Script, Code, Count, Price, Ratio, Link};
+
+#[test]
+fn test_cdata_section() {
+    let doc: Script = parse("<script><![CDATA[a < b && c]]></script>").unwrap();
+    assert_eq!(doc.xml_string.0, "a < b && c");
+
+    // References are not decoded in CDATA sections.
+    let doc: Script = parse("<script><![CDATA[&amp;]]></script>").unwrap();
+    assert_eq!(doc.xml_string.0, "&amp;");
+
+    let doc: Count = parse("<count><!-- comment --><![CDATA[42]]></count>").unwrap();
+    assert_eq!(doc.int.0, 42);
+}
+
+#[test]
+fn test_text_and_cdata() {
+    let doc: Script = parse("<script>if (a &lt; b) <![CDATA[{ c && d }]]> else</script>").unwrap();
+    assert_eq!(doc.xml_string.0, "if (a < b) { c && d } else");
+
+    let doc: Code = parse("<code>A<![CDATA[B]]>&#x43;</code>").unwrap();
+    assert_eq!(doc.token.0, "ABC");
+}
+
+#[test]
+fn test_typed_segments() {
+    let doc: Count = parse("<count>1<![CDATA[2]]></count>").unwrap();
+    assert_eq!(doc.int.0, 12);
+    let doc: Count = parse("<count>&#49;</count>").unwrap();
+    assert_eq!(doc.int.0, 1);
+
+    let doc: Price = parse("<price>1<![CDATA[2.]]>&#53;</price>").unwrap();
+    assert_eq!(doc.decimal.0.to_string(), "12.5");
+
+    let doc: Ratio = parse("<ratio><![CDATA[0.]]>&#x35;</ratio>").unwrap();
+    assert_eq!(doc.float.0, 0.5);
+
+    let doc: Link = parse("<link>http://example.org/?a=1&amp;b=<![CDATA[2]]></link>").unwrap();
+    assert_eq!(doc.any_uri.0, "http://example.org/?a=1&b=2");
+}
+
+#[test]
+fn test_split_by_comment() {
+    let doc: Script = parse("<script><![CDATA[a]]><!-- comment --><![CDATA[b]]></script>").unwrap();
+    assert_eq!(doc.xml_string.0, "ab");
+
+    let doc: Count = parse("<count>1<!-- x -->2</count>").unwrap();
+    assert_eq!(doc.int.0, 12);
+
+    let doc: Count = parse("<count>1<?pi x?>2</count>").unwrap();
+    assert_eq!(doc.int.0, 12);
+}
@@ -0,0 +1,44 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::empty::unqualified::{Br, Note, Shape};
+
+#[test]
+fn test_empty_content_with_attributes() {
+    let input = r#"<shape><point x="1" y="2"/><point x="3" y="4"> <!-- comment --> </point><marker/></shape>"#;
+    assert!(parse::<Shape>(input).is_some());
+
+    let input = r#"<shape><point x="1" y="2">text</point></shape>"#;
+    assert!(parse::<Shape>(input).is_none());
+
+    let input = r#"<shape><point x="1" y="2"><point/></point></shape>"#;
+    assert!(parse::<Shape>(input).is_none());
+}
+
+#[test]
+fn test_untyped_local_element() {
+    let input = r#"<shape><point/><marker></marker></shape>"#;
+    assert!(parse::<Shape>(input).is_some());
+
+    // marker has no type, so it is an xs:anyType.
+    let input = r#"<shape><point/><marker kind="dot">text <b>bold</b> <point/></marker></shape>"#;
+    assert!(parse::<Shape>(input).is_some());
+
+    let input = r#"<shape><point/><marker/><marker/></shape>"#;
+    assert!(parse::<Shape>(input).is_none());
+}
+
+#[test]
+fn test_untyped_toplevel_element() {
+    // note has no type, so it is an xs:anyType.
+    assert!(parse::<Note>("<note/>").is_some());
+    assert!(parse::<Note>(r#"<note lang="en">Call <b>me</b> back</note>"#).is_some());
+}
+
+#[test]
+fn test_empty_content_without_attributes() {
+    assert!(parse::<Br>("<br/>").is_some());
+    assert!(parse::<Br>("<br></br>").is_some());
+    assert!(parse::<Br>("<br><br/></br>").is_none());
+}
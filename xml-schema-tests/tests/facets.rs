@@ -0,0 +1,58 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::facets::unqualified::Discount;
+
+#[test]
+fn test_derived_facets() {
+    let input = r#"<discount rate="10" code="ab"/>"#;
+    let doc: Discount = parse(input).unwrap();
+    assert!(doc.attr_rate.is_some());
+    assert!(doc.attr_code.is_some());
+}
+
+#[test]
+fn test_derived_bound_wins() {
+    assert!(parse::<Discount>(r#"<discount rate="50"/>"#).is_none());
+}
+
+#[test]
+fn test_inherited_bound() {
+    assert!(parse::<Discount>(r#"<discount rate="-1"/>"#).is_none());
+}
+
+#[test]
+fn test_derived_max_length() {
+    assert!(parse::<Discount>(r#"<discount code="abcde"/>"#).is_none());
+}
+
+#[test]
+fn test_derived_min_length() {
+    assert!(parse::<Discount>(r#"<discount code="a"/>"#).is_none());
+}
+
+#[test]
+fn test_patterns_of_all_steps() {
+    let doc: Discount = parse(r#"<discount sku="246-AB"/>"#).unwrap();
+    assert!(doc.attr_sku.is_some());
+    // Only matches the base's pattern
+    assert!(parse::<Discount>(r#"<discount sku="135-AB"/>"#).is_none());
+    // Only matches the derived type's pattern
+    assert!(parse::<Discount>(r#"<discount sku="2468-AB"/>"#).is_none());
+    assert!(parse::<Discount>(r#"<discount sku="246-ab"/>"#).is_none());
+}
+
+#[test]
+fn test_patterns_of_one_step() {
+    // Values must match one of the patterns of a restriction.
+    assert!(parse::<Discount>(r##"<discount color="#00ff00"/>"##).is_some());
+    assert!(parse::<Discount>(r#"<discount color="green"/>"#).is_some());
+    assert!(parse::<Discount>(r#"<discount color="Green"/>"#).is_none());
+}
+
+#[test]
+fn test_pattern_of_integer() {
+    assert!(parse::<Discount>(r#"<discount quantity="12"/>"#).is_some());
+    assert!(parse::<Discount>(r#"<discount quantity="13"/>"#).is_none());
+}
@@ -0,0 +1,68 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::floats::unqualified::Measurement;
+
+#[test]
+fn test_float() {
+    let input = r#"<measurement single="0.1" double="0.1" confidence="1E0"/>"#;
+    let doc: Measurement = parse(input).unwrap();
+    let single = doc.attr_single.unwrap();
+    let double = doc.attr_double.unwrap();
+    assert_eq!(single.0, 0.1f32);
+    assert_eq!(double.0, 0.1f64);
+    assert_eq!(single.to_string(), "1.0E-1");
+    assert_eq!(double.to_string(), "1.0E-1");
+    assert_eq!(doc.attr_confidence.unwrap().0.to_string(), "1.0E0");
+}
+
+#[test]
+fn test_special_values() {
+    let input = r#"<measurement single="-INF" double="NaN"/>"#;
+    let doc: Measurement = parse(input).unwrap();
+    assert_eq!(doc.attr_single.unwrap().to_string(), "-INF");
+    assert!(doc.attr_double.unwrap().0.is_nan());
+
+    let input = r#"<measurement single="+INF" double="-0"/>"#;
+    let doc: Measurement = parse(input).unwrap();
+    assert_eq!(doc.attr_single.unwrap().to_string(), "INF");
+    assert_eq!(doc.attr_double.unwrap().to_string(), "-0.0E0");
+}
+
+#[test]
+fn test_lexical_forms() {
+    let input = r#"<measurement single="12.5e+3" double=".5"/>"#;
+    let doc: Measurement = parse(input).unwrap();
+    assert_eq!(doc.attr_single.unwrap().to_string(), "1.25E4");
+    assert_eq!(doc.attr_double.unwrap().to_string(), "5.0E-1");
+}
+
+#[test]
+fn test_invalid_lexical_form() {
+    assert!(parse::<Measurement>(r#"<measurement single="inf"/>"#).is_none());
+}
+
+#[test]
+fn test_missing_exponent() {
+    assert!(parse::<Measurement>(r#"<measurement single="1e"/>"#).is_none());
+}
+
+#[test]
+fn test_facets_in_value_space() {
+    // 0.1 rounds to the same float as the maxInclusive facet.
+    let input = r#"<measurement tenth="0.1"/>"#;
+    let doc: Measurement = parse(input).unwrap();
+    let tenth = doc.attr_tenth.unwrap();
+    assert_eq!((tenth.0).0, 0.1f32);
+}
+
+#[test]
+fn test_facet_out_of_range() {
+    assert!(parse::<Measurement>(r#"<measurement confidence="1.5"/>"#).is_none());
+}
+
+#[test]
+fn test_nan_is_incomparable() {
+    assert!(parse::<Measurement>(r#"<measurement confidence="NaN"/>"#).is_none());
+}
@@ -0,0 +1,78 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::{ParseXml, DefaultParseContext, IdError, IdTable, InnerStream, Tokenizer};
+use xml_schema_tests::parse_with;
+use xml_schema_tests::ids::unqualified::{Book, Library, Pile, Shelf};
+
+fn parse_ids<'input>(input: &'input str) -> IdTable<'input> {
+    let mut parse_context = DefaultParseContext::default();
+    parse_with::<Library, _>(input, &mut parse_context, &Default::default()).unwrap();
+    parse_context.into_ids()
+}
+
+#[test]
+fn test_ids() {
+    let input = r#"
+    <library>
+        <book id="b1"><title>foo</title></book>
+        <book id="b2" sequel="b1" related="b1 b3"><title>bar</title></book>
+        <book id="b3"><title>baz</title></book>
+    </library>"#;
+    let tokenizer = Tokenizer::from(input);
+    let mut stream = Box::new(InnerStream::new(tokenizer));
+    let mut parse_context = DefaultParseContext::default();
+    let library = Library::parse_xml(&mut stream, &mut parse_context, &Default::default()).unwrap();
+    assert_eq!(library.book.len(), 3);
+    let ids = parse_context.into_ids();
+    assert_eq!(ids.check(), Ok(()));
+    assert!(ids.contains("b2"));
+    assert!(!ids.contains("b4"));
+
+    let book: Book = ids.get_element("b2", &mut stream, &mut DefaultParseContext::default()).unwrap();
+    assert_eq!(book.attr_id.0, "b2");
+    assert_eq!(book.attr_sequel.unwrap().0, "b1");
+    assert!(ids.get_element::<Book, _>("b4", &mut stream, &mut DefaultParseContext::default()).is_none());
+}
+
+#[test]
+fn test_duplicate_id() {
+    let input = r#"
+    <library>
+        <book id="b1"><title>foo</title></book>
+        <book id="b1"><title>bar</title></book>
+    </library>"#;
+    assert_eq!(parse_ids(input).check(), Err(vec![IdError::DuplicateId("b1")]));
+}
+
+#[test]
+fn test_dangling_idref() {
+    let input = r#"
+    <library>
+        <book id="b1" sequel="b3"><title>foo</title></book>
+        <book id="b2" related="b1 b4"><title>bar</title></book>
+    </library>"#;
+    assert_eq!(parse_ids(input).check(), Err(vec![IdError::DanglingIdref("b3"), IdError::DanglingIdref("b4")]));
+}
+
+#[test]
+fn test_rolled_back_ids() {
+    // The book is first parsed as the start of the sequence, which fails
+    // without an owner, then skipped by the wildcard.
+    let input = r#"<shelf><book id="b1" sequel="b2"><title>foo</title></book></shelf>"#;
+    let mut parse_context = DefaultParseContext::default();
+    parse_with::<Shelf, _>(input, &mut parse_context, &Default::default()).unwrap();
+    let ids = parse_context.into_ids();
+    assert!(!ids.contains("b1"));
+    assert_eq!(ids.check(), Ok(()));
+}
+
+#[test]
+fn test_element_before_wildcard() {
+    // The book is matched by its declaration rather than skipped by the
+    // wildcard declared before it.
+    let input = r#"<pile><book id="b1"><title>foo</title></book></pile>"#;
+    let mut parse_context = DefaultParseContext::default();
+    parse_with::<Pile, _>(input, &mut parse_context, &Default::default()).unwrap();
+    assert!(parse_context.ids().contains("b1"));
+}
@@ -0,0 +1,53 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::integers::unqualified::Counter;
+
+#[test]
+fn test_native_integers() {
+    let input = r#"<counter offset="-9223372036854775808" port="+8080" level="-128"/>"#;
+    let doc: Counter = parse(input).unwrap();
+    assert_eq!(doc.attr_offset.unwrap().0, i64::min_value());
+    assert_eq!(doc.attr_port.unwrap().0, 8080u16);
+    assert_eq!((doc.attr_level.unwrap().0).0, -128i8);
+}
+
+#[test]
+fn test_arbitrary_precision_integer() {
+    let input = r#"<counter total="-123456789012345678901234567890"/>"#;
+    let doc: Counter = parse(input).unwrap();
+    assert_eq!(doc.attr_total.unwrap().0.to_string(), "-123456789012345678901234567890");
+}
+
+#[test]
+fn test_arbitrary_precision_non_negative_integer() {
+    let input = r#"<counter count="123456789012345678901234567890"/>"#;
+    let doc: Counter = parse(input).unwrap();
+    assert_eq!(doc.attr_count.unwrap().0.to_string(), "123456789012345678901234567890");
+    assert!(parse::<Counter>(r#"<counter count="-1"/>"#).is_none());
+}
+
+#[test]
+fn test_long_overflow() {
+    assert!(parse::<Counter>(r#"<counter offset="9223372036854775808"/>"#).is_none());
+}
+
+#[test]
+fn test_negative_unsigned() {
+    assert!(parse::<Counter>(r#"<counter port="-1"/>"#).is_none());
+    let doc: Counter = parse(r#"<counter port="-00"/>"#).unwrap();
+    assert_eq!(doc.attr_port.unwrap().0, 0u16);
+}
+
+#[test]
+fn test_restricted_native_integer() {
+    assert!(parse::<Counter>(r#"<counter level="11"/>"#).is_none());
+}
+
+#[test]
+fn test_enumerated_native_integer() {
+    assert!(parse::<Counter>(r#"<counter step="01"/>"#).is_some());
+    assert!(parse::<Counter>(r#"<counter step="10"/>"#).is_some());
+    assert!(parse::<Counter>(r#"<counter step="2"/>"#).is_none());
+}
@@ -0,0 +1,42 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::lists::unqualified::Sample;
+
+#[test]
+fn test_list_whitespace() {
+    let input = "<sample values=\"  1.5\t2\n 3 \" levels=\"\"/>";
+    let doc: Sample = parse(input).unwrap();
+    let values = doc.attr_values.unwrap();
+    assert_eq!(values.0.len(), 3);
+    assert_eq!(values.to_string(), "1.5E0 2.0E0 3.0E0");
+    assert_eq!(doc.attr_levels.unwrap().0.len(), 0);
+}
+
+#[test]
+fn test_list_length() {
+    let input = r#"<sample pair="1 2" levels="1 10"/>"#;
+    let doc: Sample = parse(input).unwrap();
+    assert_eq!((doc.attr_pair.unwrap().0).0.len(), 2);
+    assert_eq!(doc.attr_levels.unwrap().0.len(), 2);
+}
+
+#[test]
+fn test_list_too_long() {
+    assert!(parse::<Sample>(r#"<sample pair="1 2 3"/>"#).is_none());
+}
+
+#[test]
+fn test_list_item_facets() {
+    assert!(parse::<Sample>(r#"<sample levels="1 20"/>"#).is_none());
+}
+
+#[test]
+fn test_list_pattern() {
+    // The pattern applies to the whole list, with whitespace collapsed.
+    let doc: Sample = parse("<sample range=\" 1\t 10 \"/>").unwrap();
+    assert_eq!((doc.attr_range.unwrap().0).0.len(), 2);
+    assert!(parse::<Sample>(r#"<sample range="1 2 3"/>"#).is_none());
+    assert!(parse::<Sample>(r#"<sample range="1"/>"#).is_none());
+}
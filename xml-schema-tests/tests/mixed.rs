@@ -0,0 +1,129 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::{MixedItem, DefaultParseContext};
+use xml_schema_tests::{parse, parse_with};
+use xml_schema_tests::mixed::unqualified::{Description, DescriptionChild, Item};
+
+#[test]
+fn test_mixed_content() {
+    let input = r#"<description>A <emphasis>very</emphasis> nice <link>thing</link>.</description>"#;
+    let doc: Description = parse(input).unwrap();
+    assert_eq!(doc.mixed.len(), 5);
+    match doc.mixed[0] {
+        MixedItem::Text(ref text) if text == "A " => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+    match doc.mixed[1] {
+        MixedItem::Element(DescriptionChild::Emphasis(_)) => (),
+        ref item => panic!("Expected emphasis, got {:?}", item),
+    }
+    match doc.mixed[2] {
+        MixedItem::Text(ref text) if text == " nice " => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+    match doc.mixed[3] {
+        MixedItem::Element(DescriptionChild::Link(_)) => (),
+        ref item => panic!("Expected link, got {:?}", item),
+    }
+    match doc.mixed[4] {
+        MixedItem::Text(ref text) if text == "." => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+}
+
+#[test]
+fn test_mixed_content_without_children() {
+    let input = r#"<description>Nothing special</description>"#;
+    let doc: Description = parse(input).unwrap();
+    assert_eq!(doc.mixed.len(), 1);
+    match doc.mixed[0] {
+        MixedItem::Text(ref text) if text == "Nothing special" => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+
+    let input = r#"<description/>"#;
+    let doc: Description = parse(input).unwrap();
+    assert!(doc.mixed.is_empty());
+}
+
+#[test]
+fn test_element_only_content() {
+    let input = r#"
+    <item>
+        <name>foo</name>
+        <description>bar</description>
+    </item>"#;
+    assert!(parse::<Item>(input).is_some());
+
+    let input = r#"
+    <item>
+        <name>foo</name>
+        stray text
+        <description>bar</description>
+    </item>"#;
+    assert!(parse::<Item>(input).is_none());
+
+    let input = r#"
+    <item>
+        <name>foo</name>
+        <description>bar</description>
+        stray text
+    </item>"#;
+    assert!(parse::<Item>(input).is_none());
+}
+
+#[test]
+fn test_element_only_content_rejects_cdata() {
+    let input = r#"<item><![CDATA[stray text]]><name>foo</name><description>bar</description></item>"#;
+    assert!(parse::<Item>(input).is_none());
+}
+
+#[test]
+fn test_simple_content() {
+    let input = r#"<item><name><!-- comment -->foo</name><description>bar</description></item>"#;
+    assert!(parse::<Item>(input).is_some());
+
+    let input = r#"<item><name></name><description>bar</description></item>"#;
+    assert!(parse::<Item>(input).is_some());
+
+    let input = r#"<item><name>foo<emphasis>bar</emphasis></name><description>bar</description></item>"#;
+    assert!(parse::<Item>(input).is_none());
+
+    let input = r#"<item><name><emphasis>bar</emphasis></name><description>bar</description></item>"#;
+    assert!(parse::<Item>(input).is_none());
+}
+
+#[test]
+fn test_mixed_content_references() {
+    let input = r#"<description>AT&amp;T <emphasis>&#233;</emphasis></description>"#;
+    let doc: Description = parse(input).unwrap();
+    match doc.mixed[0] {
+        MixedItem::Text(ref text) if text == "AT&T " => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+}
+
+#[test]
+fn test_mixed_content_cdata_line_ends() {
+    let input = "<description>a<![CDATA[b\r\nc\rd]]></description>";
+    let doc: Description = parse(input).unwrap();
+    match doc.mixed[1] {
+        MixedItem::Text(ref text) if text == "b\nc\nd" => (),
+        ref item => panic!("Expected text, got {:?}", item),
+    }
+}
+
+#[test]
+fn test_mixed_content_parsed_once() {
+    let input = r#"<description>See <anchor id="a1"/>.</description>"#;
+    let mut parse_context = DefaultParseContext::default();
+    let doc: Description = parse_with(input, &mut parse_context, &Default::default()).unwrap();
+    assert_eq!(doc.mixed.len(), 3);
+    match doc.mixed[1] {
+        MixedItem::Element(DescriptionChild::Anchor(ref anchor)) => assert!(anchor.attr_id.is_some()),
+        ref item => panic!("Expected anchor, got {:?}", item),
+    }
+    // The anchor would be a duplicate of itself if it was parsed again.
+    assert_eq!(parse_context.into_ids().check(), Ok(()));
+}
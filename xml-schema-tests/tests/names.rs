@@ -0,0 +1,71 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::{ParseXmlStr, DefaultParseContext, Facets, Language};
+use xml_schema_tests::parse;
+use xml_schema_tests::names::unqualified::{Entry, Lang};
+
+fn is_language(value: &str) -> bool {
+    match Language::parse_xml_str(value, &mut DefaultParseContext::default(), &Default::default(), &Facets::default()) {
+        Some(("", _)) => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn test_names() {
+    let input = r#"<entry name="xsl:template" key="-1.5" keys=" a  b.c " lang="en-GB" label="a label" entity="logo"/>"#;
+    let doc: Entry = parse(input).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "xsl:template");
+    assert_eq!(doc.attr_key.unwrap().0, "-1.5");
+    let keys: Vec<_> = doc.attr_keys.unwrap().0.iter().map(|key| key.0).collect();
+    assert_eq!(keys, vec!["a", "b.c"]);
+    assert_eq!(doc.attr_lang.unwrap().0, "en-GB");
+    assert_eq!(doc.attr_label.unwrap().0, "a label");
+    assert_eq!(doc.attr_entity.unwrap().0, "logo");
+}
+
+#[test]
+fn test_collapsed_names() {
+    let doc: Lang = parse("<lang> en </lang>").unwrap();
+    assert_eq!(doc.language.0, "en");
+    let input = "<entry name=\" xsl:template \" key=\" a.b \" entity=\"\tlogo\"/>";
+    let doc: Entry = parse(input).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "xsl:template");
+    assert_eq!(doc.attr_key.unwrap().0, "a.b");
+    assert_eq!(doc.attr_entity.unwrap().0, "logo");
+}
+
+#[test]
+fn test_invalid_name() {
+    assert!(parse::<Entry>(r#"<entry name="1st"/>"#).is_none());
+}
+
+#[test]
+fn test_empty_nmtokens() {
+    assert!(parse::<Entry>(r#"<entry keys=" "/>"#).is_none());
+}
+
+#[test]
+fn test_normalized_string() {
+    // Tabs are replaced with spaces, like in the attribute value.
+    let doc: Entry = parse("<entry label=\"a\tlabel\"/>").unwrap();
+    assert_eq!(doc.attr_label.unwrap().0, "a label");
+}
+
+#[test]
+fn test_invalid_entity() {
+    assert!(parse::<Entry>(r#"<entry entity="a:b"/>"#).is_none());
+}
+
+#[test]
+fn test_language_tags() {
+    for tag in &["en", "en-US", "zh-Hant-TW", "sr-Latn-RS", "de-CH-1901", "es-419",
+                 "zh-yue-HK", "en-a-bbb-x-a-ccc", "x-whatever", "i-klingon", "hy-Latn-IT-arevela"] {
+        assert!(is_language(tag), "{} should be a language tag", tag);
+    }
+    for tag in &["", "e", "en-", "-en", "1en", "en-US-a", "en-x", "en-verylongsubtag",
+                 "en_US", "abcdefghi"] {
+        assert!(!is_language(tag), "{} should not be a language tag", tag);
+    }
+}
@@ -0,0 +1,97 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::DefaultParseContext;
+use xml_schema_tests::{parse, parse_with};
+use xml_schema_tests::namespaces::NOTATIONS;
+use xml_schema_tests::namespaces::lib::Book;
+
+#[test]
+fn test_declaration_after_attributes() {
+    let input = r#"<lib:book id="1" lib:edition="2" xmlns:lib="urn:example:library"><lib:title>Dune</lib:title></lib:book>"#;
+    let doc: Book = parse(input).unwrap();
+    assert!(doc.attr_id.is_some());
+    assert!(doc.attr_edition.is_some());
+    assert_eq!(doc.attrs.len(), 2); // Namespace declarations are not attributes
+}
+
+#[test]
+fn test_unprefixed_attributes_have_no_namespace() {
+    let input = r#"<book xmlns="urn:example:library" id="1"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_some());
+
+    // The default namespace does not apply to attributes.
+    let input = r#"<book xmlns="urn:example:library" edition="2"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
+
+#[test]
+fn test_undeclared_default_namespace() {
+    let input = r#"<book xmlns="urn:example:library"><title xmlns="">Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
+
+#[test]
+fn test_unknown_prefix() {
+    let input = r#"<lib:book xmlns:lib="urn:example:library" other:id="1"><lib:title>Dune</lib:title></lib:book>"#;
+    assert!(parse::<Book>(input).is_none());
+
+    let input = r#"<other:book><other:title>Dune</other:title></other:book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
+
+#[test]
+fn test_duplicate_expanded_names() {
+    let input = r#"<lib:book xmlns:lib="urn:example:library" xmlns:l="urn:example:library" lib:edition="1" l:edition="2"><lib:title>Dune</lib:title></lib:book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
+
+#[test]
+fn test_reserved_prefixes() {
+    let input = r#"<book xmlns="urn:example:library" xmlns:xml="http://www.w3.org/XML/1998/namespace"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_some());
+
+    let input = r#"<book xmlns="urn:example:library" xmlns:xml="urn:example:library"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_none());
+
+    let input = r#"<book xmlns="urn:example:library" xmlns:xmlns="urn:example:library"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_none());
+
+    let input = r#"<book xmlns="urn:example:library" xmlns:x="http://www.w3.org/XML/1998/namespace"><title>Dune</title></book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
+
+#[test]
+fn test_unprefixed_notation_in_default_namespace() {
+    let input = r#"<book xmlns="urn:example:library" cover="gif"><title>Dune</title></book>"#;
+    let doc: Book = parse_with(input, &mut DefaultParseContext::with_notations(NOTATIONS), &Default::default()).unwrap();
+    assert_eq!(doc.attr_cover.unwrap().0.name.namespace, Some("urn:example:library"));
+
+    // Without a default namespace, the name is in no namespace.
+    let input = r#"<lib:book xmlns:lib="urn:example:library" cover="gif"><lib:title>Dune</lib:title></lib:book>"#;
+    let doc: Option<Book> = parse_with(input, &mut DefaultParseContext::with_notations(NOTATIONS), &Default::default());
+    assert!(doc.is_none());
+    let input = r#"<lib:book xmlns:lib="urn:example:library" cover="lib:gif"><lib:title>Dune</lib:title></lib:book>"#;
+    let doc: Option<Book> = parse_with(input, &mut DefaultParseContext::with_notations(NOTATIONS), &Default::default());
+    assert!(doc.is_some());
+}
+
+#[test]
+fn test_qname_values() {
+    let input = r#"<lib:book xmlns:lib="urn:example:library" genre="lib:fiction"><lib:title>Dune</lib:title></lib:book>"#;
+    let doc: Book = parse(input).unwrap();
+    let genre = doc.attr_genre.unwrap();
+    assert_eq!(genre.namespace, Some("urn:example:library"));
+    assert_eq!(genre.local_name, "fiction");
+
+    // Unprefixed names are in the default namespace.
+    let input = r#"<book xmlns="urn:example:library" genre="fiction"><title>Dune</title></book>"#;
+    let doc: Book = parse(input).unwrap();
+    assert_eq!(doc.attr_genre.unwrap().namespace, Some("urn:example:library"));
+}
+
+#[test]
+fn test_qname_with_unbound_prefix() {
+    let input = r#"<lib:book xmlns:lib="urn:example:library" genre="other:fiction"><lib:title>Dune</lib:title></lib:book>"#;
+    assert!(parse::<Book>(input).is_none());
+}
@@ -0,0 +1,34 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::DefaultParseContext;
+use xml_schema_tests::{parse, parse_with};
+use xml_schema_tests::notation::NOTATIONS;
+use xml_schema_tests::notation::unqualified::Picture;
+
+#[test]
+fn test_notation_table() {
+    assert_eq!(NOTATIONS.len(), 2);
+    assert_eq!(NOTATIONS[0].name, "gif");
+    assert_eq!(NOTATIONS[0].public, Some("image/gif"));
+    assert_eq!(NOTATIONS[0].system, None);
+    assert_eq!(NOTATIONS[1].name, "png");
+    assert_eq!(NOTATIONS[1].system, Some("viewer"));
+}
+
+#[test]
+fn test_notation() {
+    let input = r#"<picture format="png"/>"#;
+    let doc: Picture = parse_with(input, &mut DefaultParseContext::with_notations(NOTATIONS), &Default::default()).unwrap();
+    let format = &doc.attr_format.0;
+    assert_eq!(format.name.local_name, "png");
+    assert_eq!(format.declaration.public, Some("image/png"));
+    assert_eq!(format.declaration.system, Some("viewer"));
+}
+
+#[test]
+fn test_undeclared_notation() {
+    // The parse context knows no notation.
+    let input = r#"<picture format="png"/>"#;
+    assert!(parse::<Picture>(input).is_none());
+}
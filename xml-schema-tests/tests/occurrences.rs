@@ -0,0 +1,33 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::occurrences::unqualified::Palette;
+
+#[test]
+fn test_choice_within_bounds() {
+    let input = r#"<palette><color>red</color><shade>dark</shade><color>blue</color><note>a</note></palette>"#;
+    let doc: Option<Palette> = parse(input);
+    assert!(doc.is_some());
+}
+
+#[test]
+fn test_choice_too_few() {
+    let input = r#"<palette><color>red</color></palette>"#;
+    let doc: Option<Palette> = parse(input);
+    assert!(doc.is_none());
+}
+
+#[test]
+fn test_choice_too_many() {
+    let input = r#"<palette><color>red</color><color>green</color><color>blue</color><shade>dark</shade></palette>"#;
+    let doc: Option<Palette> = parse(input);
+    assert!(doc.is_none());
+}
+
+#[test]
+fn test_group_ref_too_many() {
+    let input = r#"<palette><color>red</color><color>blue</color><note>a</note><note>b</note><note>c</note></palette>"#;
+    let doc: Option<Palette> = parse(input);
+    assert!(doc.is_none());
+}
@@ -0,0 +1,64 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::open_content::unqualified::{Interleaved, Suffixed};
+
+#[test]
+fn test_interleave() {
+    let input = r#"
+    <interleaved xmlns:foo="http://example.org/foo">
+        <foo:bar />
+        <first>1</first>
+        <foo:baz>qux</foo:baz>
+        <second>2</second>
+        <foo:quux />
+    </interleaved>"#;
+    let doc: Interleaved = parse(input).unwrap();
+    assert_eq!(doc.open_content.len(), 3);
+}
+
+#[test]
+fn test_interleave_rejects_unqualified() {
+    // ##other excludes the absent namespace.
+    let input = r#"
+    <interleaved>
+        <first>1</first>
+        <bar />
+        <second>2</second>
+    </interleaved>"#;
+    assert!(parse::<Interleaved>(input).is_none());
+}
+
+#[test]
+fn test_suffix() {
+    let input = r#"
+    <suffixed xmlns:foo="http://example.org/foo">
+        <first>1</first>
+        <second>2</second>
+        <foo:bar />
+        <foo:baz />
+    </suffixed>"#;
+    let doc: Suffixed = parse(input).unwrap();
+    assert_eq!(doc.open_content.len(), 2);
+
+    let input = r#"
+    <suffixed xmlns:foo="http://example.org/foo">
+        <first>1</first>
+        <foo:bar />
+        <second>2</second>
+    </suffixed>"#;
+    assert!(parse::<Suffixed>(input).is_none());
+}
+
+#[test]
+fn test_unbound_prefix_in_open_content() {
+    let input = r#"
+    <interleaved>
+        <first>1</first>
+        <foo:bar />
+        <second>2</second>
+    </interleaved>"#;
+    assert!(parse::<Interleaved>(input).is_none());
+}
+
@@ -2,7 +2,7 @@ extern crate xml_schema;
 extern crate xml_schema_tests;
 
 use xml_schema::support::{ParseXml, DefaultParseContext, InnerStream, Tokenizer};
-use xml_schema_tests::po;
+use xml_schema_tests::{parse, po};
 
 const EXAMPLE: &'static str = r#"
 <?xml version="1.0"?>
@@ -47,3 +47,32 @@ fn test_example() {
     let order = order.unwrap();
     assert_eq!(order.attr_order_date.unwrap().0, "1999-10-20");
 }
+
+#[test]
+fn test_unknown_attribute() {
+    let input = EXAMPLE.replace(r#"<shipTo country="US">"#, r#"<shipTo country="US" county="Marin">"#);
+    assert!(parse::<po::unqualified::PurchaseOrder>(&input).is_none());
+}
+
+#[test]
+fn test_duplicate_attribute() {
+    let input = EXAMPLE.replace(r#"<shipTo country="US">"#, r#"<shipTo country="US" country="US">"#);
+    assert!(parse::<po::unqualified::PurchaseOrder>(&input).is_none());
+}
+
+#[test]
+fn test_missing_required_attribute() {
+    let input = EXAMPLE.replace(r#"<item partNum="926-AA">"#, r#"<item>"#);
+    assert!(parse::<po::unqualified::PurchaseOrder>(&input).is_none());
+}
+
+#[test]
+fn test_instance_attributes() {
+    // xsi: and xml: attributes need no declaration nor wildcard.
+    let input = EXAMPLE.replace(r#"<purchaseOrder orderDate="1999-10-20">"#, r#"<purchaseOrder orderDate="1999-10-20" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:po po.xsd">"#);
+    let input = input.replace(r#"<shipTo country="US">"#, r#"<shipTo country="US" xml:lang="en" xml:space="preserve">"#);
+    assert!(parse::<po::unqualified::PurchaseOrder>(&input).is_some());
+
+    let input = EXAMPLE.replace(r#"<shipTo country="US">"#, r#"<shipTo country="US" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:foo="bar">"#);
+    assert!(parse::<po::unqualified::PurchaseOrder>(&input).is_none());
+}
@@ -0,0 +1,159 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::FullName;
+use xml_schema_tests::parse;
+use xml_schema_tests::references::unqualified::{Company, Slogan};
+
+#[test]
+fn test_references() {
+    let doc: Company = parse(r#"<company name="AT&amp;T &#233;&#xE9;" ticker="&#x54;"/>"#).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "AT&T éé");
+    assert_eq!(doc.attr_ticker.unwrap().0, "T");
+
+    let doc: Company = parse(r#"<company name="plain"/>"#).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "plain");
+}
+
+#[test]
+fn test_malformed_references() {
+    assert!(parse::<Company>(r#"<company name="&unknown;"/>"#).is_none());
+    assert!(parse::<Company>(r#"<company name="&#0;"/>"#).is_none());
+    assert!(parse::<Company>(r#"<company name="&#x+41;"/>"#).is_none());
+}
+
+#[test]
+fn test_attribute_value_normalization() {
+    let doc: Company = parse("<company name=\"a\tb\r\nc\" ticker=\"a&#32;b\"/>").unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "a b c");
+    assert_eq!(doc.attr_ticker.unwrap().0, "a b");
+
+    // Whitespace characters written as references are kept.
+    let doc: Company = parse(r#"<company name="a&#10;b"/>"#).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "a\nb");
+}
+
+#[test]
+fn test_typed_attribute_references() {
+    let input = r#"<company founded="&#49;98&#x30;" website="http://example.org/?a=1&amp;b=2"/>"#;
+    let doc: Company = parse(input).unwrap();
+    assert_eq!(doc.attr_founded.unwrap().0, 1980);
+    assert_eq!(doc.attr_website.unwrap().0, "http://example.org/?a=1&b=2");
+    assert_eq!(doc.attrs[&FullName::new(None, "website")], "http://example.org/?a=1&b=2");
+}
+
+#[test]
+fn test_token_whitespace_reference() {
+    assert!(parse::<Company>(r#"<company ticker="a&#10;b"/>"#).is_none());
+}
+
+#[test]
+fn test_wildcard_attribute_values() {
+    let doc: Company = parse(r#"<company motto="x &lt; y"/>"#).unwrap();
+    assert_eq!(doc.attrs[&FullName::new(None, "motto")], "x < y");
+}
+
+#[test]
+fn test_internal_entities() {
+    let input = r#"<!DOCTYPE company [
+        <!ENTITY company "Example Corp">
+        <!ENTITY full "&company; &amp; Sons">
+        <!ENTITY company "Ignored Corp">
+    ]>
+    <company name="&full;" motto="&company;"/>"#;
+    let doc: Company = parse(input).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "Example Corp & Sons");
+    assert_eq!(doc.attrs[&FullName::new(None, "motto")], "Example Corp");
+
+    let input = r#"<!DOCTYPE slogan [ <!ENTITY company "Example Corp"> ]>
+    <slogan>Trust &company;</slogan>"#;
+    let doc: Slogan = parse(input).unwrap();
+    assert_eq!(doc.xml_string.0, "Trust Example Corp");
+}
+
+#[test]
+fn test_entity_replacement_text() {
+    // Character references are replaced when the entity is declared, and
+    // the entity reference they form is expanded where it is used.
+    let input = r#"<!DOCTYPE slogan [ <!ENTITY e "&#38;amp;"> ]>
+    <slogan>&e;</slogan>"#;
+    let doc: Slogan = parse(input).unwrap();
+    assert_eq!(doc.xml_string.0, "&");
+
+    let input = r#"<!DOCTYPE slogan [ <!ENTITY e "&#38;"> ]>
+    <slogan>&e;</slogan>"#;
+    assert!(parse::<Slogan>(input).is_none());
+
+    let input = r#"<!DOCTYPE slogan [ <!ENTITY e "&#xD800;"> ]>
+    <slogan>&e;</slogan>"#;
+    assert!(parse::<Slogan>(input).is_none());
+}
+
+#[test]
+fn test_markup_in_attribute_entity() {
+    let input = r#"<!DOCTYPE company [ <!ENTITY lt2 "&#60;"> ]>
+    <company name="a &lt2; b"/>"#;
+    assert!(parse::<Company>(input).is_none());
+
+    let input = r#"<!DOCTYPE company [ <!ENTITY cdata "&#60;![CDATA[Example Corp]]>"> ]>
+    <company name="&cdata;"/>"#;
+    assert!(parse::<Company>(input).is_none());
+
+    // A character reference in the value itself is only data.
+    let input = r#"<company name="a &#60; b"/>"#;
+    let doc: Company = parse(input).unwrap();
+    assert_eq!(doc.attr_name.unwrap().0, "a < b");
+}
+
+#[test]
+fn test_unknown_entity() {
+    let input = r#"<!DOCTYPE company [ <!ENTITY company "Example Corp"> ]>
+    <company name="&other;"/>"#;
+    assert!(parse::<Company>(input).is_none());
+}
+
+#[test]
+fn test_recursive_entities() {
+    let input = r#"<!DOCTYPE company [
+        <!ENTITY a "&b;">
+        <!ENTITY b "&a;">
+    ]>
+    <company name="&a;"/>"#;
+    assert!(parse::<Company>(input).is_none());
+}
+
+#[test]
+fn test_entity_expansion_limit() {
+    let input = r#"<!DOCTYPE company [
+        <!ENTITY lol "lol">
+        <!ENTITY lol1 "&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;">
+        <!ENTITY lol2 "&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;">
+        <!ENTITY lol3 "&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;">
+        <!ENTITY lol4 "&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;">
+        <!ENTITY lol5 "&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;">
+        <!ENTITY lol6 "&lol5;&lol5;&lol5;&lol5;&lol5;&lol5;&lol5;&lol5;&lol5;&lol5;">
+        <!ENTITY lol7 "&lol6;&lol6;&lol6;&lol6;&lol6;&lol6;&lol6;&lol6;&lol6;&lol6;">
+        <!ENTITY lol8 "&lol7;&lol7;&lol7;&lol7;&lol7;&lol7;&lol7;&lol7;&lol7;&lol7;">
+        <!ENTITY lol9 "&lol8;&lol8;&lol8;&lol8;&lol8;&lol8;&lol8;&lol8;&lol8;&lol8;">
+    ]>
+    <company name="&lol9;"/>"#;
+    assert!(parse::<Company>(input).is_none());
+}
+
+#[test]
+fn test_entity_expansion_limit_per_document() {
+    // Each value is below the limit, but not all of them together.
+    let input = r#"<!DOCTYPE company [
+        <!ENTITY lol "lol">
+        <!ENTITY lol1 "&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;">
+        <!ENTITY lol2 "&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;">
+        <!ENTITY lol3 "&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;">
+        <!ENTITY lol4 "&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;">
+        <!ENTITY lol5 "&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;">
+    ]>
+    <company name="&lol5;"/>"#;
+    assert!(parse::<Company>(input).is_some());
+
+    let input = input.replace(r#"name="&lol5;""#, r#"name="&lol5;" a="&lol5;" b="&lol5;" c="&lol5;""#);
+    assert!(parse::<Company>(&input).is_none());
+}
@@ -0,0 +1,91 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema_tests::parse;
+use xml_schema_tests::substitution::unqualified::{Drawing, Shape, ShapeHead, BlockedShape};
+
+#[test]
+fn test_abstract_head() {
+    let input = r#"<shape><label>foo</label></shape>"#;
+    assert!(parse::<ShapeHead>(input).is_none());
+
+    let input = r#"
+    <drawing>
+        <shape><label>foo</label></shape>
+    </drawing>"#;
+    assert!(parse::<Drawing>(input).is_none());
+}
+
+#[test]
+fn test_substitution() {
+    let input = r#"
+    <drawing>
+        <square><label>foo</label></square>
+        <circle><label>bar</label><radius>1</radius></circle>
+        <blockedShape><label>baz</label></blockedShape>
+        <blockedSquare><label>qux</label></blockedSquare>
+    </drawing>"#;
+    let doc: Drawing = parse(input).unwrap();
+    assert_eq!(doc.shape.len(), 2);
+    match doc.shape[0] {
+        Shape::Square(_) => (),
+        ref s => panic!("Expected square, got {:?}", s),
+    }
+    match doc.shape[1] {
+        Shape::Circle(_) => (),
+        ref s => panic!("Expected circle, got {:?}", s),
+    }
+    assert_eq!(doc.blocked_shape.len(), 2);
+    match doc.blocked_shape[1] {
+        BlockedShape::BlockedSquare(_) => (),
+        ref s => panic!("Expected blockedSquare, got {:?}", s),
+    }
+}
+
+#[test]
+fn test_transitive_substitution() {
+    // roundedSquare is in the substitution group of square, itself in the
+    // one of shape.
+    let input = r#"
+    <drawing>
+        <roundedSquare><label>foo</label></roundedSquare>
+        <square><label>bar</label></square>
+    </drawing>"#;
+    let doc: Drawing = parse(input).unwrap();
+    assert_eq!(doc.shape.len(), 2);
+    match doc.shape[0] {
+        Shape::RoundedSquare(_) => (),
+        ref s => panic!("Expected roundedSquare, got {:?}", s),
+    }
+    match doc.shape[1] {
+        Shape::Square(_) => (),
+        ref s => panic!("Expected square, got {:?}", s),
+    }
+}
+
+#[test]
+fn test_block_extension() {
+    // blockedShape blocks members whose type is derived by extension.
+    let input = r#"
+    <drawing>
+        <blockedCircle><label>foo</label><radius>1</radius></blockedCircle>
+    </drawing>"#;
+    assert!(parse::<Drawing>(input).is_none());
+}
+
+#[test]
+fn test_xsi_type_refused() {
+    // Type substitution is not supported, so blocked derivations can't
+    // be used through xsi:type either.
+    let input = r#"
+    <drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+        <blockedShape xsi:type="circleType"><label>foo</label><radius>1</radius></blockedShape>
+    </drawing>"#;
+    assert!(parse::<Drawing>(input).is_none());
+
+    let input = r#"
+    <drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+        <square xsi:type="shapeType"><label>foo</label></square>
+    </drawing>"#;
+    assert!(parse::<Drawing>(input).is_none());
+}
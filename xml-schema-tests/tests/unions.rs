@@ -0,0 +1,48 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::names::FullName;
+use xml_schema::processor::SCHEMA_URI;
+use xml_schema_tests::parse;
+use xml_schema_tests::unions::unqualified::Shirt;
+
+#[test]
+fn test_union_member_order() {
+    let doc: Shirt = parse(r#"<shirt size="12"/>"#).unwrap();
+    assert_eq!(doc.attr_size.unwrap().member_type(), Some(FullName::new(Some(SCHEMA_URI), "int")));
+}
+
+#[test]
+fn test_union_full_match() {
+    let doc: Shirt = parse(r#"<shirt size="12XL"/>"#).unwrap();
+    assert_eq!(doc.attr_size.unwrap().member_type(), Some(FullName::new(Some(SCHEMA_URI), "string")));
+}
+
+#[test]
+fn test_union_enumeration() {
+    let doc: Shirt = parse(r#"<shirt shortSize="small"/>"#).unwrap();
+    assert_eq!(doc.attr_short_size.unwrap().0.member_type(), Some(FullName::new(Some(SCHEMA_URI), "string")));
+    let doc: Shirt = parse(r#"<shirt shortSize="1"/>"#).unwrap();
+    assert_eq!(doc.attr_short_size.unwrap().0.member_type(), Some(FullName::new(Some(SCHEMA_URI), "int")));
+}
+
+#[test]
+fn test_union_enumeration_mismatch() {
+    assert!(parse::<Shirt>(r#"<shirt shortSize="large"/>"#).is_none());
+}
+
+#[test]
+fn test_union_enumeration_value_space() {
+    let doc: Shirt = parse(r#"<shirt shortSize="+01"/>"#).unwrap();
+    assert_eq!(doc.attr_short_size.unwrap().0.member_type(), Some(FullName::new(Some(SCHEMA_URI), "int")));
+    assert!(parse::<Shirt>(r#"<shirt shortSize="2"/>"#).is_none());
+    assert!(parse::<Shirt>(r#"<shirt shortSize="Small"/>"#).is_none());
+}
+
+#[test]
+fn test_union_pattern() {
+    let doc: Shirt = parse(r#"<shirt letterSize="XXL"/>"#).unwrap();
+    assert_eq!(doc.attr_letter_size.unwrap().0.member_type(), Some(FullName::new(Some(SCHEMA_URI), "string")));
+    assert!(parse::<Shirt>(r#"<shirt letterSize="12"/>"#).is_none());
+    assert!(parse::<Shirt>(r#"<shirt letterSize="XLL"/>"#).is_none());
+}
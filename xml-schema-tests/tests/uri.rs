@@ -0,0 +1,52 @@
+extern crate xml_schema;
+extern crate xml_schema_tests;
+
+use xml_schema::support::{ParentContext, DefaultParseContext};
+use xml_schema_tests::{parse, parse_with};
+use xml_schema_tests::uri::unqualified::Links;
+
+#[test]
+fn test_components() {
+    let input = r#"<links><link href="http://example.org/a/b?c=d#e"/></links>"#;
+    let links = parse::<Links>(input).unwrap();
+    let uri = links.link[0].attr_href.components().unwrap();
+    assert_eq!(uri.scheme, Some("http"));
+    assert_eq!(uri.authority, Some("example.org"));
+    assert_eq!(uri.path, "/a/b");
+    assert_eq!(uri.query, Some("c=d"));
+    assert_eq!(uri.fragment, Some("e"));
+}
+
+#[test]
+fn test_resolution() {
+    let input = r#"
+    <links xml:base="/docs/">
+        <link href="intro.html"/>
+        <link xml:base="api/v1/" href="../index.html#top"/>
+        <link href="mailto:someone@example.org"/>
+    </links>"#;
+    let links = parse_with::<Links, _>(input, &mut DefaultParseContext::default(), &ParentContext::with_base("http://example.org/index.xml")).unwrap();
+    let resolved: Vec<_> = links.link.iter().map(|link| link.attr_href.resolve().unwrap()).collect();
+    assert_eq!(resolved, vec![
+        "http://example.org/docs/intro.html",
+        "http://example.org/docs/api/index.html#top",
+        "mailto:someone@example.org",
+        ]);
+
+    // Without a document URI, references are relative to xml:base only.
+    let links = parse::<Links>(input).unwrap();
+    assert_eq!(links.link[0].attr_href.resolve().unwrap(), "/docs/intro.html");
+}
+
+#[test]
+fn test_whitespace_in_uri() {
+    let input = "<links><link href=\"  ../a  b/\u{e9}\n\"/></links>";
+    let links = parse::<Links>(input).unwrap();
+    assert_eq!(links.link[0].attr_href.0, "../a b/\u{e9}");
+    assert_eq!(links.link[0].attr_href.components().unwrap().path, "../a b/\u{e9}");
+}
+
+#[test]
+fn test_invalid_uri() {
+    assert!(parse::<Links>(r#"<links><link href="http://example.org:80a/"/></links>"#).is_none());
+}
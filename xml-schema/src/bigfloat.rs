@@ -49,21 +49,39 @@ impl PartialOrd for BigFloat {
         else {
             match (self, rhs) {
                 (NaN, _) | (_, NaN) => None,
-                (PlusInfinity, _) => Some(Ordering::Less),
-                (MinusInfinity, _) => Some(Ordering::Greater),
-                (_, PlusInfinity) => Some(Ordering::Greater),
-                (_, MinusInfinity) => Some(Ordering::Less),
+                (PlusInfinity, _) => Some(Ordering::Greater),
+                (MinusInfinity, _) => Some(Ordering::Less),
+                (_, PlusInfinity) => Some(Ordering::Less),
+                (_, MinusInfinity) => Some(Ordering::Greater),
                 (Decimal(l), Decimal(r)) => l.partial_cmp(r),
             }
         }
     }
 }
+impl BigFloat {
+    /// Rounds to the nearest value of a binary floating-point type
+    /// (`f32` for `float`, `f64` for `double`).
+    ///
+    /// Returns `None` if `F` does not parse the decimal representation.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-2/#f-floatPtRound
+    pub fn round<F: FromStr>(&self) -> Option<F> {
+        use self::BigFloat::*;
+        let rounded = match self {
+            NaN => "NaN".parse(),
+            PlusInfinity => "inf".parse(),
+            MinusInfinity => "-inf".parse(),
+            Decimal(d) => d.to_string().parse(),
+        };
+        rounded.ok()
+    }
+}
 impl Display for BigFloat {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> Result<(), ::std::fmt::Error> {
         use self::BigFloat::*;
         match self {
             NaN => write!(f, "NaN"),
-            PlusInfinity => write!(f, "+INF"), // TODO: what's the canonical repr?
+            PlusInfinity => write!(f, "INF"),
             MinusInfinity => write!(f, "-INF"),
             Decimal(d) => d.fmt(f),
         }
@@ -78,6 +96,11 @@ impl Ord for BigFloatNotNaN {
         self.0.partial_cmp(&rhs.0).expect("NaN")
     }
 }
+impl BigFloatNotNaN {
+    pub fn round<F: FromStr>(&self) -> Option<F> {
+        self.0.round()
+    }
+}
 impl From<BigDecimal> for BigFloatNotNaN {
     fn from(d: BigDecimal) -> BigFloatNotNaN {
         BigFloatNotNaN(BigFloat::Decimal(d))
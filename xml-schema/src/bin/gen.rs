@@ -41,14 +41,15 @@ fn main() {
 
     let mut documents = Vec::new();
     for (filename, input) in inputs2 {
-        documents.push((filename, parse_xsd_with_visitor(input, &mut parse_context).unwrap()));
+        documents.push((filename, input, parse_xsd_with_visitor(input, &mut parse_context).unwrap()));
     }
 
     let mut processors = Vec::new();
-    for (filename, document) in &documents {
+    for (filename, input, document) in &documents {
         println!("// Input: {:?}", filename);
         let mut proc = Processor::new(document);
         proc.process_ast(document);
+        proc.check_unique_particle_attribution(input);
         processors.push(proc);
     }
 
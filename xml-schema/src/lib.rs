@@ -5,6 +5,7 @@ extern crate codegen;
 extern crate heck;
 extern crate num_traits;
 extern crate bigdecimal;
+extern crate regex;
 
 #[macro_use] pub mod macros;
 pub mod xml_utils;
@@ -12,15 +13,25 @@ pub mod names;
 pub mod support;
 pub mod primitives;
 pub mod bigfloat;
+pub mod uri;
+pub mod patterns;
 
 pub mod parser;
 pub mod processor;
+pub mod upa;
+pub mod restriction;
 pub mod parser_generator;
 
 #[cfg(test)]
 mod test_parser;
 #[cfg(test)]
 mod test_parser_schema;
+#[cfg(test)]
+mod test_processor;
+#[cfg(test)]
+mod test_uri;
+#[cfg(test)]
+mod test_patterns;
 
 use support::{ParseXml, InnerStream, ParseContext, ParentContext};
 
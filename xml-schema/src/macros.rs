@@ -7,11 +7,11 @@ macro_rules! use_xsd_types {
 
 #[macro_export]
 macro_rules! try_rollback {
-    ($stream:expr, $tx:expr, $e:expr) => {
+    ($stream:expr, $parse_context:expr, $tx:expr, $e:expr) => {
         match $e {
             Some(i) => i,
             None => {
-                $tx.rollback($stream);
+                $tx.rollback_with($stream, $parse_context);
                 return None
             }
         }
@@ -45,7 +45,7 @@ macro_rules! impl_enum {
                     }
                 )*
 
-                tx.rollback(stream);
+                tx.rollback_with(stream, parse_context);
                 None
             }
         }
@@ -61,11 +61,9 @@ macro_rules! impl_singleton_variant {
     ( $enum_name:ident, __empty_element, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Option < Box < $type_name:ident > > ) => {
         Some(super::$type_mod_name::$type_name::parse_empty($parse_context, $parent_context).map(Box::new).map($enum_name::$variant_name))
     };
-    ( $enum_name:ident, __empty_element, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Vec < $type_name:ident > ) => {{
-        // TODO: Should it be vec![], or vec![super::$type_mod_name::$type_name::parse_empty(...)]?
-        let mut items = Vec::new();
-        Some($enum_name::$variant_name(items))
-    }};
+    ( $enum_name:ident, __empty_element, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; $( max=$max:expr ; )* > ) => {
+        parse_empty_occurrences::<super::$type_mod_name::$type_name, _>($parse_context, $parent_context, $min).map($enum_name::$variant_name)
+    };
 
     // non-empty element; call parse_xml
     ( $enum_name:ident, $stream:expr, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Box < $type_name:ident > ) => {
@@ -74,13 +72,12 @@ macro_rules! impl_singleton_variant {
     ( $enum_name:ident, $stream:expr, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Option < Box < $type_name:ident > > ) => {
         Some(super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context).map(Box::new).map($enum_name::$variant_name))
     };
-    ( $enum_name:ident, $stream:expr, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Vec < $type_name:ident > ) => {{
-        let mut items = Vec::new();
-        while let Some(item) = super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context) {
-            items.push(item);
-        }
-        Some($enum_name::$variant_name(items))
-    }}
+    ( $enum_name:ident, $stream:expr, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; max=$max:expr ; > ) => {
+        parse_occurrences::<super::$type_mod_name::$type_name, _>($stream, $parse_context, $parent_context, $min, $max).map($enum_name::$variant_name)
+    };
+    ( $enum_name:ident, $stream:expr, $parse_context:expr, $parent_context:expr, $variant_name:ident, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; > ) => {
+        parse_occurrences::<super::$type_mod_name::$type_name, _>($stream, $parse_context, $parent_context, $min, ::std::usize::MAX).map($enum_name::$variant_name)
+    };
 }
 
 // TODO: deduplicate the empty and non-empty cases
@@ -140,11 +137,9 @@ macro_rules! impl_struct_variant_field {
     ( __empty_element, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Option < Box < $type_name:ident > > ) => {
         Some(super::$type_mod_name::$type_name::parse_empty($parse_context, $parent_context).map(Box::new))
     };
-    ( __empty_element, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Vec < $type_name:ident > ) => {{
-        // TODO: Should it be vec![], or vec![super::$type_mod_name::$type_name::parse_empty(...)]?
-        let mut items = Vec::new();
-        Some(items)
-    }};
+    ( __empty_element, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; $( max=$max:expr ; )* > ) => {
+        parse_empty_occurrences::<super::$type_mod_name::$type_name, _>($parse_context, $parent_context, $min)
+    };
 
 
     ( $stream: expr, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Box < $type_name:ident > ) => {
@@ -153,13 +148,12 @@ macro_rules! impl_struct_variant_field {
     ( $stream: expr, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Option < Box < $type_name:ident > > ) => {
         Some(super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context).map(Box::new))
     };
-    ( $stream: expr, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Vec < $type_name:ident > ) => {{
-        let mut items = Vec::new();
-        while let Some(item) = super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context) {
-            items.push(item);
-        }
-        Some(items)
-    }}
+    ( $stream: expr, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; max=$max:expr ; > ) => {
+        parse_occurrences::<super::$type_mod_name::$type_name, _>($stream, $parse_context, $parent_context, $min, $max)
+    };
+    ( $stream: expr, $parse_context:expr, $parent_context:expr,  $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; > ) => {
+        parse_occurrences::<super::$type_mod_name::$type_name, _>($stream, $parse_context, $parent_context, $min, ::std::usize::MAX)
+    };
 }
 
 #[macro_export]
@@ -209,7 +203,7 @@ macro_rules! impl_group_or_sequence {
 
 #[macro_export]
 macro_rules! impl_element {
-    ( $struct_name:ident, $namespace:expr, $name:expr, attributes = { $( ($attr_prefix:expr, $attr_local:expr) => $attr_name:ident : $use:ident, )* }, fields = { $( ( $field_name:ident, $( $field_args:tt )* ), )* } ) => {
+    ( $struct_name:ident, $namespace:expr, $name:expr, attributes = { $( ($attr_namespace:expr, $attr_local:expr) => $attr_name:ident : $use:ident, )* }, any_attributes = $any_attributes:expr, open_content = $open_content:expr, mixed = $mixed:expr, fields = { $( ( $field_name:ident, $( $field_args:tt )* ), )* } ) => {
         #[allow(unused_imports)]
         use $crate::support::*;
         impl<'input> ParseXml<'input> for $struct_name<'input> {
@@ -222,6 +216,9 @@ macro_rules! impl_element {
             #[allow(unused_variables)]
             fn parse_self_xml<'b, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &'b ParentContext<'input>) -> Option<Self> {
                 use $crate::support::{XmlToken,ElementEnd};
+                // Elements of the parent's interleaved open content may
+                // appear before this one.
+                skip_open_content(stream, parse_context, parent_context);
                 let mut parent_context: ParentContext<'input> = parent_context.clone();
                 let tx = stream.transaction();
                 let mut tok = stream.next().unwrap();
@@ -229,132 +226,204 @@ macro_rules! impl_element {
                     match tok {
                         XmlToken::Whitespaces(_) => (),
                         XmlToken::Comment(_) => (),
-                        XmlToken::Text(_) => (),
+                        XmlToken::Text(_) | XmlToken::Cdata(_) if stream.allows_text() => (),
                         _ => break,
                     }
                     tok = stream.next().unwrap();
                 }
                 match tok {
+                    XmlToken::Text(_) | XmlToken::Cdata(_) => {
+                        // Character data in element-only content
+                        tx.rollback_with(stream, parse_context);
+                        return None
+                    },
                     XmlToken::ElementStart(element_prefix, name) => {
                         if name.to_str() == $name {
+                            parent_context.element = Some(stream.element_start());
                             let mut attrs = HashMap::new();
                             $(
                                 let mut $attr_name = None;
                             )*
-                            loop {
-                                let tok = stream.next().unwrap();
-                                match tok {
+                            // Attributes as written, with their values decoded
+                            // and normalized.
+                            let mut raw_attrs = Vec::new();
+                            let end_tok = loop {
+                                match stream.next().unwrap() {
                                     XmlToken::Whitespaces(_) => (),
                                     XmlToken::Comment(_) => (),
                                     XmlToken::Text(_) => (),
                                     XmlToken::Attribute((key_prefix, key_local), value) => {
-                                        let key_prefix = key_prefix.to_str();
-                                        let key_local = key_local.to_str();
-                                        let value = value.to_str();
-                                        match (key_prefix, key_local) {
-                                            ("xmlns", l) => {
-                                                parent_context.namespaces.insert(l, value);
-                                                parse_context.on_xmlns(Some(l), value);
-                                                //continue; // TODO: uncomment
+                                        match stream.decode(value.to_str(), $crate::xml_utils::TextKind::Attribute) {
+                                            Some(value) => raw_attrs.push((key_prefix.to_str(), key_local.to_str(), value)),
+                                            None => {
+                                                // Malformed reference
+                                                tx.rollback_with(stream, parse_context);
+                                                return None;
                                             },
-                                            ("", "xmlns") => {
-                                                parent_context.namespaces.insert("", value);
-                                                parse_context.on_xmlns(None, value);
-                                                //continue; // TODO: uncomment
-                                            }
-                                            _ => (),
-                                        }
-                                        let key_namespace = match key_prefix {
-                                            "" => parent_context.namespaces.get(element_prefix.to_str()).cloned(),
-                                            _ => parent_context.namespaces.get(key_prefix).cloned(),
-                                        };
-                                        let key = FullName::new(key_namespace, key_local);
-                                        let old = attrs.insert(key, value); assert_eq!(old, None);
-                                        match (key_prefix, key_local) {
-                                            $(
-                                                (_, $attr_local) => { // TODO: match the namespace too
-                                                    match ParseXmlStr::parse_xml_str(value, parse_context, &parent_context, &Facets::default()) {
-                                                        Some(("", value)) => {
-                                                            $attr_name = Some(value)
-                                                            // TODO: check for duplicates
-                                                        },
-                                                        Some((out, _)) =>
-                                                            panic!("Unmatched data at the end of {}={:?}: {:?}", $attr_local, value, out),
-                                                        None => 
-                                                            panic!("Could not parse {}={:?}.", $attr_local, value),
-                                                    }
-                                                },
-                                            )*
-                                            _ => (), // TODO: unknown attribute
                                         }
                                     },
-                                    XmlToken::ElementEnd(ElementEnd::Open) => {
-                                        let element_ns: &'input str = match element_prefix.to_str() {
-                                            "" => {
-                                                parent_context.namespaces.get("").cloned().unwrap_or("")
-                                            },
-                                            p => {
-                                                parent_context.namespaces.get(p)
-                                                .expect(&format!("unknown namespace {:?}", element_prefix.to_str())).clone()
-                                            },
-                                        };
-                                        if element_ns != $namespace { // This can't be checked on the ElementStart, because we have to check for xmlns first.
-                                            return None
-                                        }
-                                        let ret = Some($struct_name {
-                                            attrs,
-                                            $(
-                                                $attr_name: extract_attribute!($attr_name, $attr_local, $use),
-                                            )*
-                                            $(
-                                                $field_name: impl_element_field!(stream, tx, parse_context, &parent_context, $($field_args)*),
-                                            )*
-                                        });
-                                        let mut next_tok;
-                                        loop {
-                                            next_tok = stream.next();
-                                            match next_tok {
-                                                Some(XmlToken::Whitespaces(_)) => (),
-                                                Some(XmlToken::Comment(_)) => (),
-                                                Some(XmlToken::Text(_)) => (),
-                                                Some(XmlToken::ElementEnd(ElementEnd::Close(prefix2, name2))) => {
-                                                    assert_eq!((element_prefix.to_str(), name.to_str()), (prefix2.to_str(), name2.to_str()));
-                                                    return ret;
-                                                }
-                                                _ => panic!(format!("Expected closing tag for {}:{}, got {:?}", element_prefix, name, next_tok)),
+                                    tok => break tok,
+                                }
+                            };
+                            // Namespace declarations and xml:base apply to all
+                            // attributes of their element, whatever their order,
+                            // so they are read first.
+                            for &(key_prefix, key_local, value) in raw_attrs.iter() {
+                                let declared = match (key_prefix, key_local) {
+                                    ("xmlns", l) => parent_context.declare_namespace(l, value).map(|()| Some(l)),
+                                    ("", "xmlns") => parent_context.declare_namespace("", value).map(|()| None),
+                                    ("xml", "base") => {
+                                        parent_context.set_base(value);
+                                        continue;
+                                    },
+                                    _ => continue,
+                                };
+                                match declared {
+                                    Ok(prefix) => if !parent_context.check_only {
+                                        parse_context.on_xmlns(prefix, value)
+                                    },
+                                    Err(_) => {
+                                        // Reserved prefix or namespace
+                                        tx.rollback_with(stream, parse_context);
+                                        return None;
+                                    },
+                                }
+                            }
+                            for &(key_prefix, key_local, value) in raw_attrs.iter() {
+                                // Unprefixed attributes are in no namespace, whatever
+                                // the namespace of their element.
+                                let key_namespace = match (key_prefix, key_local) {
+                                    ("xmlns", _) | ("", "xmlns") => continue,
+                                    ("", _) => None,
+                                    _ => match parent_context.resolve_prefix(key_prefix) {
+                                        Ok(namespace) => namespace,
+                                        Err(_) => {
+                                            tx.rollback_with(stream, parse_context);
+                                            return None;
+                                        },
+                                    },
+                                };
+                                let key = FullName::new(key_namespace, key_local);
+                                if attrs.insert(key, value).is_some() {
+                                    // Duplicate attribute, possibly with two prefixes
+                                    // bound to the same namespace.
+                                    tx.rollback_with(stream, parse_context);
+                                    return None;
+                                }
+                                match (key_namespace.unwrap_or(""), key_local) {
+                                    $(
+                                        ($attr_namespace, $attr_local) => {
+                                            match ParseXmlStr::parse_xml_str(value, parse_context, &parent_context, &Facets::default()) {
+                                                Some(("", value)) => {
+                                                    $attr_name = Some(value)
+                                                },
+                                                _ => {
+                                                    // Not a valid value of the attribute's
+                                                    // type, or unmatched data at its end.
+                                                    tx.rollback_with(stream, parse_context);
+                                                    return None;
+                                                },
                                             }
-                                        }
+                                        },
+                                    )*
+                                    (XSI_URI, "type") => {
+                                        // Type substitution is not supported, so
+                                        // elements naming their type are refused,
+                                        // whatever the block of their declaration.
+                                        tx.rollback_with(stream, parse_context);
+                                        return None;
                                     },
-                                    XmlToken::ElementEnd(ElementEnd::Empty) => {
-                                        let element_ns: &'input str = parent_context.namespaces.get(element_prefix.to_str()).expect(&format!("unknown namespace {:?}", element_prefix.to_str())).clone();
-                                        if element_ns != $namespace { // This can't be checked on the ElementStart, because we have to check for xmlns first.
-                                            return None
-                                        }
-                                        return Some($struct_name {
-                                            attrs,
-                                            $(
-                                                $attr_name: extract_attribute!($attr_name, $attr_local, $use),
-                                            )*
-                                            $(
-                                                $field_name: impl_empty_element_field!(parse_context, &parent_context, $($field_args)*),
-                                            )*
-                                        });
+                                    (XSI_URI, "nil") | (XSI_URI, "schemaLocation") |
+                                    (XSI_URI, "noNamespaceSchemaLocation") | (XML_URI, _) => {
+                                        // Allowed on every element, whether it is
+                                        // declared or not.
                                     },
-                                    XmlToken::ElementEnd(ElementEnd::Close(_, _)) => {
-                                        tx.rollback(stream);
-                                        return None
+                                    _ => {
+                                        let any_attributes: Option<Wildcard> = $any_attributes;
+                                        match any_attributes {
+                                            Some(ref wildcard) if wildcard.allows(&key) => (),
+                                            _ => {
+                                                // Undeclared attribute, and there is no
+                                                // wildcard to allow it.
+                                                tx.rollback_with(stream, parse_context);
+                                                return None;
+                                            },
+                                        }
                                     },
-                                    _ => panic!(format!("Expected element end for {}:{}, got {:?}", element_prefix, name, tok)),
                                 }
                             }
+                            // This can't be checked on the ElementStart, because
+                            // the element may declare its own namespace.
+                            let element_ns: &'input str = match parent_context.resolve_prefix(element_prefix.to_str()) {
+                                Ok(namespace) => namespace.unwrap_or(""),
+                                Err(_) => {
+                                    tx.rollback_with(stream, parse_context);
+                                    return None;
+                                },
+                            };
+                            if element_ns != $namespace {
+                                tx.rollback_with(stream, parse_context);
+                                return None
+                            }
+                            match end_tok {
+                                XmlToken::ElementEnd(ElementEnd::Open) => {
+                                    let open_content: Option<OpenContent> = $open_content;
+                                    stream.start_content(open_content, $mixed);
+                                    let ret = Some($struct_name {
+                                        attrs,
+                                        $(
+                                            $attr_name: extract_attribute!(stream, parse_context, tx, $attr_name, $attr_local, $use),
+                                        )*
+                                        $(
+                                            $field_name: impl_element_field!(stream, tx, parse_context, &parent_context, $($field_args)*),
+                                        )*
+                                    });
+                                    let mut next_tok;
+                                    loop {
+                                        next_tok = stream.next();
+                                        match next_tok {
+                                            Some(XmlToken::Whitespaces(_)) => (),
+                                            Some(XmlToken::Comment(_)) => (),
+                                            Some(XmlToken::Text(_)) |
+                                            Some(XmlToken::Cdata(_)) if stream.allows_text() => (),
+                                            Some(XmlToken::ElementEnd(ElementEnd::Close(prefix2, name2))) => {
+                                                assert_eq!((element_prefix.to_str(), name.to_str()), (prefix2.to_str(), name2.to_str()));
+                                                return ret;
+                                            }
+                                            _ => {
+                                                // Content does not match the content model
+                                                // (eg. an invalid child made a field stop early).
+                                                tx.rollback_with(stream, parse_context);
+                                                return None;
+                                            },
+                                        }
+                                    }
+                                },
+                                XmlToken::ElementEnd(ElementEnd::Empty) => {
+                                    return Some($struct_name {
+                                        attrs,
+                                        $(
+                                            $attr_name: extract_attribute!(stream, parse_context, tx, $attr_name, $attr_local, $use),
+                                        )*
+                                        $(
+                                            $field_name: impl_empty_element_field!(parse_context, &parent_context, $($field_args)*),
+                                        )*
+                                    });
+                                },
+                                XmlToken::ElementEnd(ElementEnd::Close(_, _)) => {
+                                    tx.rollback_with(stream, parse_context);
+                                    return None
+                                },
+                                tok => panic!(format!("Expected element end for {}:{}, got {:?}", element_prefix, name, tok)),
+                            }
                         }
                         else {
-                            tx.rollback(stream);
+                            tx.rollback_with(stream, parse_context);
                             None
                         }
                     },
                     XmlToken::ElementEnd(ElementEnd::Close(_, _)) => {
-                        tx.rollback(stream);
+                        tx.rollback_with(stream, parse_context);
                         return None
                     },
                     _ => panic!(format!("Expected element start for {}, got {:?}", Self::NODE_NAME, tok)),
@@ -364,12 +433,29 @@ macro_rules! impl_element {
     }
 }
 
+// Abstract elements and elements of an abstract type never appear in
+// instances, only their substitution group members do.
+#[macro_export]
+macro_rules! impl_abstract_element {
+    ( $struct_name:ident ) => {
+        #[allow(unused_imports)]
+        use $crate::support::*;
+        impl<'input> ParseXml<'input> for $struct_name<'input> {
+            const NODE_NAME: &'static str = concat!("abstract element ", stringify!($struct_name));
+
+            fn parse_self_xml<TParseContext: ParseContext<'input>>(_stream: &mut Stream<'input>, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>) -> Option<Self> {
+                None
+            }
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! extract_attribute {
-    ( $attr_name:ident, $attr_local:expr, required ) => {
-        $attr_name.expect(&format!("Missing attribute {}", $attr_local))
+    ( $stream: expr, $parse_context:expr, $tx: expr, $attr_name:ident, $attr_local:expr, required ) => {
+        try_rollback!($stream, $parse_context, $tx, $attr_name)
     };
-    ( $attr_name:ident, $attr_local:expr, optional ) => {
+    ( $stream: expr, $parse_context:expr, $tx: expr, $attr_name:ident, $attr_local:expr, optional ) => {
         $attr_name
     };
 }
@@ -377,35 +463,38 @@ macro_rules! extract_attribute {
 #[macro_export]
 macro_rules! impl_element_field {
     ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, $type_name:ident ) => {
-        try_rollback!($stream, $tx, super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context))
+        try_rollback!($stream, $parse_context, $tx, super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context))
     };
     ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Option < $type_name:ident > ) => {
         super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context)
     };
-    ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; max=$max:expr ; > ) => {{
-        let mut items = Vec::new();
-        let min: usize = $min;
-        let max: usize = $max;
-        while let Some(item) = super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context) {
-            items.push(item);
-            if items.len() > max {
-                return None;
-            }
-        }
-        if items.len() < min {
-            return None;
+    ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, OpenContent < $type_name:ident > ) => {{
+        let items: Vec<super::$type_mod_name::$type_name> = try_rollback!($stream, $parse_context, $tx, parse_open_content($stream, $parse_context, $parent_context));
+        items
+    }};
+    ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Mixed < $type_name:ident > { $( ( $field_name:ident, $($field_args:tt)* ), )* } ) => {{
+        // The content model is checked first, then the children are parsed
+        // in document order, among the character data.
+        let parent_context: &ParentContext = $parent_context;
+        let mut check_context = parent_context.clone();
+        check_context.check_only = true;
+        $(
+            let _ = impl_element_field!($stream, $tx, $parse_context, &check_context, $($field_args)*);
+        )*
+        let items: Vec<MixedItem<super::$type_mod_name::$type_name>> = if parent_context.check_only {
+            Vec::new()
         }
+        else {
+            try_rollback!($stream, $parse_context, $tx, parse_mixed_content($stream, $parse_context, parent_context))
+        };
+        items
+    }};
+    ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; max=$max:expr ; > ) => {{
+        let items: Vec<super::$type_mod_name::$type_name> = try_rollback!($stream, $parse_context, $tx, parse_occurrences($stream, $parse_context, $parent_context, $min, $max));
         items
     }};
     ( $stream: expr, $tx: expr, $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; > ) => {{
-        let mut items = Vec::new();
-        let min: usize = $min;
-        while let Some(item) = super::$type_mod_name::$type_name::parse_xml($stream, $parse_context, $parent_context) {
-            items.push(item);
-        }
-        if items.len() < min {
-            return None;
-        }
+        let items: Vec<super::$type_mod_name::$type_name> = try_rollback!($stream, $parse_context, $tx, parse_occurrences($stream, $parse_context, $parent_context, $min, ::std::usize::MAX));
         items
     }};
 }
@@ -422,36 +511,129 @@ macro_rules! impl_empty_element_field {
     ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Option < $type_name:ident > ) => {
         None
     };
-    ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; max=$max:expr ; > ) => {{
+    ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, OpenContent < $type_name:ident > ) => {{
         Vec::new()
     }};
-    ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; > ) => {{
-        Vec::new()
+    ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Mixed < $type_name:ident > { $( ( $field_name:ident, $($field_args:tt)* ), )* } ) => {{
+        $(
+            let _ = impl_empty_element_field!($parse_context, $parent_context, $($field_args)*);
+        )*
+        let items: Vec<MixedItem<super::$type_mod_name::$type_name>> = Vec::new();
+        items
+    }};
+    ( $parse_context:expr, $parent_context:expr, $type_mod_name:ident, Vec < $type_name:ident ; min=$min:expr ; $( max=$max:expr ; )* > ) => {{
+        let items: Vec<super::$type_mod_name::$type_name> = match parse_empty_occurrences($parse_context, $parent_context, $min) {
+            Some(items) => items,
+            None => return None,
+        };
+        items
     }};
 }
 
+#[macro_export]
+macro_rules! impl_known_elements {
+    ( $name:ident, { $( ( $variant_name:ident, $type_mod_name:ident, $type_name:ident, $namespace:expr, $local:expr ), )* } ) => {
+        #[allow(unused_imports)]
+        use $crate::support::*;
+        impl<'input> ParseKnownElement<'input> for $name<'input> {
+            fn is_known_element(name: &FullName<'input>) -> bool {
+                match (name.namespace().unwrap_or(""), name.local_name()) {
+                    $(
+                        ($namespace, $local) => true,
+                    )*
+                    _ => false,
+                }
+            }
+
+            fn parse_known_element<TParseContext: ParseContext<'input>>(name: &FullName<'input>, stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self> {
+                match (name.namespace().unwrap_or(""), name.local_name()) {
+                    $(
+                        ($namespace, $local) => super::$type_mod_name::$type_name::parse_xml(stream, parse_context, parent_context).map(Box::new).map($name::$variant_name),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! impl_wildcard {
+    ( $name:ident, $wildcard:expr ) => {
+        #[allow(unused_imports)]
+        use $crate::support::*;
+        impl<'input> ParseXml<'input> for $name<'input> {
+            const NODE_NAME: &'static str = concat!("wildcard ", stringify!($name));
+
+            fn parse_self_xml<TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self> {
+                let wildcard: Wildcard = $wildcard;
+                parse_any_element(stream, parse_context, parent_context, &wildcard).map($name)
+            }
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! impl_union {
-    ( $name:ident, { $($variant_macro:ident ! ( $($variant_args: tt )* ), )* } ) => {
+    ( $name:ident, { $($variant_macro:ident ! ( $variant_name:ident $(, $member_namespace:expr, $member_local_name:expr)* ), )* } ) => {
         #[allow(unused_imports)]
         use $crate::support::*;
         impl<'input> ParseXmlStr<'input> for $name<'input> {
             const NODE_NAME: &'static str = concat!("union ", stringify!($name));
 
             fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Self)> {
+                if !union_facets_allow(input, facets) {
+                    return None;
+                }
+                let value = Self::parse_member(input, parse_context, parent_context)?;
+                if let Some(ref enumeration) = facets.enumeration {
+                    // Enumeration values are compared in the value space
+                    // of the member type accepting them, so they are
+                    // parsed the same way.
+                    let mut compare_context = CompareParseContext { notations: parse_context.notations() };
+                    let allowed = enumeration.iter().any(|allowed| {
+                        $name::parse_member(*allowed, &mut compare_context, parent_context).map_or(false, |allowed| allowed == value)
+                    });
+                    if !allowed {
+                        return None;
+                    }
+                }
+                Some(("", value))
+            }
+        }
+
+        impl<'input> $name<'input> {
+            /// The value belongs to the first member type (in declaration
+            /// order) accepting all of it.
+            fn parse_member<TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self> {
                 $(
-                    match $variant_macro!($name, input, parse_context, parent_context, facets, $($variant_args)*) {
-                        Some((o, x)) => return Some((o, x)),
+                    match $variant_macro!($name, input, parse_context, parent_context, &Facets::default(), $variant_name) {
+                        Some((o, x)) => {
+                            if o.chars().all($crate::xml_utils::is_whitespace) {
+                                return Some(x)
+                            }
+                        },
                         None => (),
                     }
                 )*
 
                 None
             }
+
+            /// Name of the member type that validated the value, or `None`
+            /// if that type is anonymous.
+            pub fn member_type(&self) -> Option<FullName<'static>> {
+                match self {
+                    $(
+                        $name::$variant_name(_) => union_member_type!($($member_namespace, $member_local_name)*),
+                    )*
+                }
+            }
         }
     }
 }
 
+#[macro_export]
 macro_rules! impl_union_variant {
     ( $name:ident, $input:expr, $parse_context:expr, $parent_context:expr, $facets:expr, $variant_name:ident) => {
         ParseXmlStr::parse_xml_str($input, $parse_context, $parent_context, $facets)
@@ -459,6 +641,12 @@ macro_rules! impl_union_variant {
     }
 }
 
+#[macro_export]
+macro_rules! union_member_type {
+    ( ) => { None };
+    ( $namespace:expr, $local_name:expr ) => { Some(FullName::new($namespace, $local_name)) };
+}
+
 #[macro_export]
 macro_rules! impl_list {
     ( $name:ident, $item_type_mod_name:ident :: $item_type:ident ) => {
@@ -469,19 +657,32 @@ macro_rules! impl_list {
 
             #[allow(unused_variables)]
             fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Self)> {
-                let mut input = input;
+                let tokens = list_items(input);
+                if !list_facets_allow(&tokens, facets) {
+                    return None;
+                }
                 let mut items = Vec::new();
-                while let Some((output, item)) = ParseXmlStr::parse_xml_str(input, parse_context, parent_context, facets) {
-                    items.push(item);
-                    if output.len() == 0 {
-                        return Some(("", $name(items)));
+                for token in tokens {
+                    // Facets of the list do not apply to its items; the
+                    // item type brings its own.
+                    match ParseXmlStr::parse_xml_str(token, parse_context, parent_context, &Facets::default())? {
+                        ("", item) => items.push(item),
+                        _ => return None,
                     }
-                    if &output[0..1] != " " {
-                        return None;
+                }
+                Some(("", $name(items)))
+            }
+        }
+
+        impl<'input> ::std::fmt::Display for $name<'input> where $item_type_mod_name::$item_type<'input>: ::std::fmt::Display {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                for (i, item) in self.0.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
                     }
-                    input = &output[1..];
+                    write!(f, "{}", item)?;
                 }
-                None
+                Ok(())
             }
         }
     }
@@ -497,10 +698,11 @@ macro_rules! impl_simpletype_restriction {
 
             #[allow(unused_variables)]
             fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Self)> {
-                let mut facets = facets.clone();
-                $(
-                    facets.$facet_name =  $facet_value.or(facets.$facet_name);
-                )*
+                let own_facets = Facets {
+                    $( $facet_name: $facet_value, )*
+                    ..Facets::default()
+                };
+                let facets = facets.restrict(&own_facets);
                 let (output, v) = ParseXmlStr::parse_xml_str(input, parse_context, parent_context, &facets)?;
                 Some((output, $name(v)))
             }
@@ -19,52 +19,14 @@ pub mod xs {
     #[allow(unused_imports)]
     use super::*;
 
-    pub type Entities<'input> = restrictions::RestrictAnySimpleType<'input>;
-
-    pub type Entity<'input> = restrictions::RestrictNcName<'input>;
-
-    pub type Id<'input> = restrictions::RestrictNcName<'input>;
-
-    pub type Idref<'input> = restrictions::RestrictNcName<'input>;
-
-    pub type Idrefs<'input> = restrictions::RestrictAnySimpleType<'input>;
-
-    pub type Nmtoken<'input> = restrictions::RestrictToken2<'input>;
-
-    pub type Nmtokens<'input> = restrictions::RestrictAnySimpleType<'input>;
-
-    pub type Name<'input> = restrictions::RestrictToken3<'input>;
-
-    pub type Byte<'input> = restrictions::RestrictShort<'input>;
-
     pub type DateTimeStamp<'input> = restrictions::RestrictDateTime<'input>;
 
     pub type DayTimeDuration<'input> = restrictions::RestrictDuration2<'input>;
 
-    pub type Int<'input> = restrictions::RestrictLong<'input>;
-
-    pub type Integer<'input> = restrictions::RestrictDecimal<'input>;
-
-    pub type Language<'input> = restrictions::RestrictToken<'input>;
-
-    pub type Long<'input> = restrictions::RestrictInteger2<'input>;
-
     pub type NegativeInteger<'input> = restrictions::RestrictNonPositiveInteger<'input>;
 
     pub type NonPositiveInteger<'input> = restrictions::RestrictInteger<'input>;
 
-    pub type NormalizedString<'input> = restrictions::RestrictString<'input>;
-
-    pub type Short<'input> = restrictions::RestrictInt<'input>;
-
-    pub type UnsignedByte<'input> = restrictions::RestrictUnsignedShort<'input>;
-
-    pub type UnsignedInt<'input> = restrictions::RestrictUnsignedLong<'input>;
-
-    pub type UnsignedLong<'input> = restrictions::RestrictNonNegativeInteger<'input>;
-
-    pub type UnsignedShort<'input> = restrictions::RestrictUnsignedInt<'input>;
-
     pub type YearMonthDuration<'input> = restrictions::RestrictDuration<'input>;
 
     pub type AllNni<'input> = unions::UnionNonNegativeIntegerNmtoken<'input>;
@@ -103,30 +65,30 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct All<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_min_occurs: Option<restrictions::Enumeration012<'input>>,
         pub attr_max_occurs: Option<restrictions::Enumeration01<'input>>,
         pub all_model: super::xs::AllModel<'input>,
     }
 
     impl_element!(All, "http://www.w3.org/2001/XMLSchema", "all", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (all_model, xs, AllModel),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct Annotation<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation_content: Vec<super::enums::AnnotationContent<'input>>,
     }
 
     impl_element!(Annotation, "http://www.w3.org/2001/XMLSchema", "annotation", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation_content, enums, Vec<AnnotationContent; min=0;>),
     });
 
@@ -134,7 +96,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Any<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_not_q_name: Option<xs::QnameList<'input>>,
         pub attr_namespace: Option<xs::NamespaceList<'input>>,
         pub attr_not_namespace: Option<restrictions::RestrictBasicNamespaceList<'input>>,
@@ -145,14 +107,14 @@ pub mod xs {
     }
 
     impl_element!(Any, "http://www.w3.org/2001/XMLSchema", "any", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "notQName") => attr_not_q_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "namespace") => attr_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "notNamespace") => attr_not_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "processContents") => attr_process_contents: optional,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "notQName") => attr_not_q_name: optional,
+        ("", "namespace") => attr_namespace: optional,
+        ("", "notNamespace") => attr_not_namespace: optional,
+        ("", "processContents") => attr_process_contents: optional,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -160,7 +122,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct AnyAttribute<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_not_q_name: Option<xs::QnameListA<'input>>,
         pub attr_namespace: Option<xs::NamespaceList<'input>>,
         pub attr_not_namespace: Option<restrictions::RestrictBasicNamespaceList<'input>>,
@@ -169,12 +131,12 @@ pub mod xs {
     }
 
     impl_element!(AnyAttribute, "http://www.w3.org/2001/XMLSchema", "anyAttribute", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "notQName") => attr_not_q_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "namespace") => attr_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "notNamespace") => attr_not_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "processContents") => attr_process_contents: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "notQName") => attr_not_q_name: optional,
+        ("", "namespace") => attr_namespace: optional,
+        ("", "notNamespace") => attr_not_namespace: optional,
+        ("", "processContents") => attr_process_contents: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -182,37 +144,41 @@ pub mod xs {
     pub struct Appinfo<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
         pub attr_source: Option<support::AnyUri<'input>>,
-        pub sequence_any: Vec<super::sequences::SequenceAny<'input>>,
+        pub mixed: Vec<support::MixedItem<'input, super::xs::AppinfoChild<'input>>>,
     }
 
     impl_element!(Appinfo, "http://www.w3.org/2001/XMLSchema", "appinfo", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "source") => attr_source: optional,
-    }, fields = {
-        (sequence_any, sequences, Vec<SequenceAny; min=0;>),
+        ("", "source") => attr_source: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = true, fields = {
+        (mixed, xs, Mixed<AppinfoChild> {
+            (sequence_any, sequences, Vec<SequenceAny; min=0;>),
+        }),
     });
 
+    pub type AppinfoChild<'input> = support::NoElement<'input>;
+
     ///  This type is extended by all types which allow annotation other than <schema> itself 
     #[derive(Debug, PartialEq)]
     pub struct Assertion<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_test: Option<support::XmlString<'input>>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Assertion, "http://www.w3.org/2001/XMLSchema", "assertion", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "test") => attr_test: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "test") => attr_test: optional,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct Attribute<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_type: Option<support::QName<'input>>,
         pub attr_default: Option<support::XmlString<'input>>,
         pub attr_fixed: Option<support::XmlString<'input>>,
@@ -223,13 +189,13 @@ pub mod xs {
     }
 
     impl_element!(Attribute, "http://www.w3.org/2001/XMLSchema", "attribute", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "type") => attr_type: optional,
-        ("http://www.w3.org/2001/XMLSchema", "default") => attr_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "inheritable") => attr_inheritable: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "type") => attr_type: optional,
+        ("", "default") => attr_default: optional,
+        ("", "fixed") => attr_fixed: optional,
+        ("", "inheritable") => attr_inheritable: optional,
+        ("", "name") => attr_name: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (local_simple_type, inline_elements, Option<LocalSimpleType>),
     });
@@ -237,16 +203,16 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct AttributeGroup<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: support::NcName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub attr_decls: super::xs::AttrDecls<'input>,
     }
 
     impl_element!(AttributeGroup, "http://www.w3.org/2001/XMLSchema", "attributeGroup", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (attr_decls, xs, AttrDecls),
     });
@@ -255,7 +221,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Choice<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_min_occurs: Option<support::NonNegativeInteger<'input>>,
         pub attr_max_occurs: Option<xs::AllNni<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -263,10 +229,10 @@ pub mod xs {
     }
 
     impl_element!(Choice, "http://www.w3.org/2001/XMLSchema", "choice", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (nested_particle, xs, Vec<NestedParticle; min=0;>),
     });
@@ -274,16 +240,16 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct ComplexContent<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_mixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub choice_restriction_extension: super::enums::ChoiceRestrictionExtension<'input>,
     }
 
     impl_element!(ComplexContent, "http://www.w3.org/2001/XMLSchema", "complexContent", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "mixed") => attr_mixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "mixed") => attr_mixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (choice_restriction_extension, enums, ChoiceRestrictionExtension),
     });
@@ -291,7 +257,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct ComplexType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: support::NcName<'input>,
         pub attr_mixed: Option<support::Boolean<'input>>,
         pub attr_abstract: Option<support::Boolean<'input>>,
@@ -303,14 +269,14 @@ pub mod xs {
     }
 
     impl_element!(ComplexType, "http://www.w3.org/2001/XMLSchema", "complexType", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-        ("http://www.w3.org/2001/XMLSchema", "mixed") => attr_mixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "abstract") => attr_abstract: optional,
-        ("http://www.w3.org/2001/XMLSchema", "final") => attr_final: optional,
-        ("http://www.w3.org/2001/XMLSchema", "block") => attr_block: optional,
-        ("http://www.w3.org/2001/XMLSchema", "defaultAttributesApply") => attr_default_attributes_apply: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: required,
+        ("", "mixed") => attr_mixed: optional,
+        ("", "abstract") => attr_abstract: optional,
+        ("", "final") => attr_final: optional,
+        ("", "block") => attr_block: optional,
+        ("", "defaultAttributesApply") => attr_default_attributes_apply: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (complex_type_model, xs, ComplexTypeModel),
     });
@@ -318,7 +284,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct DefaultOpenContent<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_applies_to_empty: Option<support::Boolean<'input>>,
         pub attr_mode: Option<restrictions::EnumerationInterleaveSuffix<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -326,10 +292,10 @@ pub mod xs {
     }
 
     impl_element!(DefaultOpenContent, "http://www.w3.org/2001/XMLSchema", "defaultOpenContent", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "appliesToEmpty") => attr_applies_to_empty: optional,
-        ("http://www.w3.org/2001/XMLSchema", "mode") => attr_mode: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "appliesToEmpty") => attr_applies_to_empty: optional,
+        ("", "mode") => attr_mode: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (any_wildcard, inline_elements, AnyWildcard),
     });
@@ -338,19 +304,25 @@ pub mod xs {
     pub struct Documentation<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
         pub attr_source: Option<support::AnyUri<'input>>,
-        pub sequence_any: Vec<super::sequences::SequenceAny<'input>>,
+        pub attr_lang: Option<support::AnySimpleType<'input>>,
+        pub mixed: Vec<support::MixedItem<'input, super::xs::DocumentationChild<'input>>>,
     }
 
     impl_element!(Documentation, "http://www.w3.org/2001/XMLSchema", "documentation", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "source") => attr_source: optional,
-    }, fields = {
-        (sequence_any, sequences, Vec<SequenceAny; min=0;>),
+        ("", "source") => attr_source: optional,
+        ("http://www.w3.org/XML/1998/namespace", "lang") => attr_lang: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = true, fields = {
+        (mixed, xs, Mixed<DocumentationChild> {
+            (sequence_any, sequences, Vec<SequenceAny; min=0;>),
+        }),
     });
 
+    pub type DocumentationChild<'input> = support::NoElement<'input>;
+
     #[derive(Debug, PartialEq)]
     pub struct Element<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_type: Option<support::QName<'input>>,
         pub attr_substitution_group: Option<lists::QNameList<'input>>,
         pub attr_default: Option<support::XmlString<'input>>,
@@ -367,17 +339,17 @@ pub mod xs {
     }
 
     impl_element!(Element, "http://www.w3.org/2001/XMLSchema", "element", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "type") => attr_type: optional,
-        ("http://www.w3.org/2001/XMLSchema", "substitutionGroup") => attr_substitution_group: optional,
-        ("http://www.w3.org/2001/XMLSchema", "default") => attr_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "nillable") => attr_nillable: optional,
-        ("http://www.w3.org/2001/XMLSchema", "abstract") => attr_abstract: optional,
-        ("http://www.w3.org/2001/XMLSchema", "final") => attr_final: optional,
-        ("http://www.w3.org/2001/XMLSchema", "block") => attr_block: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "type") => attr_type: optional,
+        ("", "substitutionGroup") => attr_substitution_group: optional,
+        ("", "default") => attr_default: optional,
+        ("", "fixed") => attr_fixed: optional,
+        ("", "nillable") => attr_nillable: optional,
+        ("", "abstract") => attr_abstract: optional,
+        ("", "final") => attr_final: optional,
+        ("", "block") => attr_block: optional,
+        ("", "name") => attr_name: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (type_, enums, Option<Type>),
         (alternative_alt_type, inline_elements, Vec<AlternativeAltType; min=0;>),
@@ -387,38 +359,37 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Enumeration<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::AnySimpleType<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Enumeration, "http://www.w3.org/2001/XMLSchema", "enumeration", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct ExplicitTimezone<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: restrictions::EnumerationOptionalRequiredProhibited<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(ExplicitTimezone, "http://www.w3.org/2001/XMLSchema", "explicitTimezone", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub enum Facet<'input> {
-        FacetHead(Box<super::xs::FacetHead<'input>>),
         MinExclusive(Box<super::xs::MinExclusive<'input>>),
         MinInclusive(Box<super::xs::MinInclusive<'input>>),
         MaxExclusive(Box<super::xs::MaxExclusive<'input>>),
@@ -436,7 +407,6 @@ pub mod xs {
     }
 
     impl_enum!(Facet,
-        impl_singleton_variant!(FacetHead, xs, Box<FacetHead>),
         impl_singleton_variant!(MinExclusive, xs, Box<MinExclusive>),
         impl_singleton_variant!(MinInclusive, xs, Box<MinInclusive>),
         impl_singleton_variant!(MaxExclusive, xs, Box<MaxExclusive>),
@@ -455,62 +425,58 @@ pub mod xs {
 
     ///  An abstract element, representing facets in general. The facets defined by this spec are substitutable for this element, and implementation-defined facets should also name this as a substitution-group head. 
     #[derive(Debug, PartialEq)]
-    pub struct FacetHead<'input> {
-        pub attrs: HashMap<FullName<'input>, &'input str>,
-    }
+    pub struct FacetHead<'input>(pub PhantomData<&'input ()>);
 
-    impl_element!(FacetHead, "http://www.w3.org/2001/XMLSchema", "facet", attributes = {
-    }, fields = {
-    });
+    impl_abstract_element!(FacetHead);
 
     ///  This type is extended by all types which allow annotation other than <schema> itself 
     #[derive(Debug, PartialEq)]
     pub struct Field<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_xpath: restrictions::RestrictToken4<'input>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Field, "http://www.w3.org/2001/XMLSchema", "field", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpath") => attr_xpath: required,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "xpath") => attr_xpath: required,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct FractionDigits<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::NonNegativeInteger<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(FractionDigits, "http://www.w3.org/2001/XMLSchema", "fractionDigits", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct Group<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: support::NcName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub choice_all_choice_sequence: super::enums::ChoiceAllChoiceSequence<'input>,
     }
 
     impl_element!(Group, "http://www.w3.org/2001/XMLSchema", "group", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (choice_all_choice_sequence, enums, ChoiceAllChoiceSequence),
     });
@@ -519,17 +485,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Import<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_namespace: Option<support::AnyUri<'input>>,
         pub attr_schema_location: Option<support::AnyUri<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Import, "http://www.w3.org/2001/XMLSchema", "import", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "namespace") => attr_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "schemaLocation") => attr_schema_location: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "namespace") => attr_namespace: optional,
+        ("", "schemaLocation") => attr_schema_location: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -537,22 +503,22 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Include<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_schema_location: support::AnyUri<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Include, "http://www.w3.org/2001/XMLSchema", "include", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "schemaLocation") => attr_schema_location: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "schemaLocation") => attr_schema_location: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct Key<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: Option<support::NcName<'input>>,
         pub attr_ref: Option<support::QName<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -560,10 +526,10 @@ pub mod xs {
     }
 
     impl_element!(Key, "http://www.w3.org/2001/XMLSchema", "key", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: optional,
+        ("", "ref") => attr_ref: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (uniqueness_spec, sequences, Option<UniquenessSpec>),
     });
@@ -571,7 +537,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Keyref<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: Option<support::NcName<'input>>,
         pub attr_ref: Option<support::QName<'input>>,
         pub attr_refer: Option<support::QName<'input>>,
@@ -580,11 +546,11 @@ pub mod xs {
     }
 
     impl_element!(Keyref, "http://www.w3.org/2001/XMLSchema", "keyref", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: optional,
-        ("http://www.w3.org/2001/XMLSchema", "refer") => attr_refer: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: optional,
+        ("", "ref") => attr_ref: optional,
+        ("", "refer") => attr_refer: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (uniqueness_spec, sequences, Option<UniquenessSpec>),
     });
@@ -592,17 +558,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Length<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::NonNegativeInteger<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Length, "http://www.w3.org/2001/XMLSchema", "length", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -611,16 +577,16 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct List<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_item_type: Option<support::QName<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub local_simple_type: Option<super::inline_elements::LocalSimpleType<'input>>,
     }
 
     impl_element!(List, "http://www.w3.org/2001/XMLSchema", "list", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "itemType") => attr_item_type: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "itemType") => attr_item_type: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (local_simple_type, inline_elements, Option<LocalSimpleType>),
     });
@@ -629,17 +595,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct MaxExclusive<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::AnySimpleType<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MaxExclusive, "http://www.w3.org/2001/XMLSchema", "maxExclusive", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -647,34 +613,34 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct MaxInclusive<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::AnySimpleType<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MaxInclusive, "http://www.w3.org/2001/XMLSchema", "maxInclusive", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct MaxLength<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::NonNegativeInteger<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MaxLength, "http://www.w3.org/2001/XMLSchema", "maxLength", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -682,17 +648,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct MinExclusive<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::AnySimpleType<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MinExclusive, "http://www.w3.org/2001/XMLSchema", "minExclusive", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -700,34 +666,34 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct MinInclusive<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::AnySimpleType<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MinInclusive, "http://www.w3.org/2001/XMLSchema", "minInclusive", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct MinLength<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::NonNegativeInteger<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(MinLength, "http://www.w3.org/2001/XMLSchema", "minLength", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -735,7 +701,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Notation<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: support::NcName<'input>,
         pub attr_public: Option<xs::Public<'input>>,
         pub attr_system: Option<support::AnyUri<'input>>,
@@ -743,27 +709,27 @@ pub mod xs {
     }
 
     impl_element!(Notation, "http://www.w3.org/2001/XMLSchema", "notation", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-        ("http://www.w3.org/2001/XMLSchema", "public") => attr_public: optional,
-        ("http://www.w3.org/2001/XMLSchema", "system") => attr_system: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: required,
+        ("", "public") => attr_public: optional,
+        ("", "system") => attr_system: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct OpenContent<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_mode: Option<restrictions::EnumerationNoneInterleaveSuffix<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub any_wildcard: Option<super::inline_elements::AnyWildcard<'input>>,
     }
 
     impl_element!(OpenContent, "http://www.w3.org/2001/XMLSchema", "openContent", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "mode") => attr_mode: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "mode") => attr_mode: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (any_wildcard, inline_elements, Option<AnyWildcard>),
     });
@@ -772,15 +738,15 @@ pub mod xs {
     pub struct Override<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
         pub attr_schema_location: support::AnyUri<'input>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub schema_top: Vec<super::xs::SchemaTop<'input>>,
     }
 
     impl_element!(Override, "http://www.w3.org/2001/XMLSchema", "override", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "schemaLocation") => attr_schema_location: required,
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "schemaLocation") => attr_schema_location: required,
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (schema_top, xs, Vec<SchemaTop; min=0;>),
     });
@@ -788,15 +754,15 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Pattern<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::XmlString<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Pattern, "http://www.w3.org/2001/XMLSchema", "pattern", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -804,14 +770,14 @@ pub mod xs {
     pub struct Redefine<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
         pub attr_schema_location: support::AnyUri<'input>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub choice_annotation_redefinable: Vec<super::enums::ChoiceAnnotationRedefinable<'input>>,
     }
 
     impl_element!(Redefine, "http://www.w3.org/2001/XMLSchema", "redefine", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "schemaLocation") => attr_schema_location: required,
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "schemaLocation") => attr_schema_location: required,
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (choice_annotation_redefinable, enums, Vec<ChoiceAnnotationRedefinable; min=0;>),
     });
 
@@ -820,16 +786,16 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Restriction<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_base: Option<support::QName<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub simple_restriction_model: super::xs::SimpleRestrictionModel<'input>,
     }
 
     impl_element!(Restriction, "http://www.w3.org/2001/XMLSchema", "restriction", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "base") => attr_base: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "base") => attr_base: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (simple_restriction_model, xs, SimpleRestrictionModel),
     });
@@ -845,23 +811,23 @@ pub mod xs {
         pub attr_element_form_default: Option<xs::FormChoice<'input>>,
         pub attr_default_attributes: Option<support::QName<'input>>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub composition: Vec<super::xs::Composition<'input>>,
         pub open_content: Option<super::sequences::AnnotatedOpenContent<'input>>,
         pub sequence_schema_top_annotation: Vec<super::sequences::SequenceSchemaTopAnnotation<'input>>,
     }
 
     impl_element!(Schema, "http://www.w3.org/2001/XMLSchema", "schema", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "targetNamespace") => attr_target_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "version") => attr_version: optional,
-        ("http://www.w3.org/2001/XMLSchema", "finalDefault") => attr_final_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "blockDefault") => attr_block_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "attributeFormDefault") => attr_attribute_form_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "elementFormDefault") => attr_element_form_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "defaultAttributes") => attr_default_attributes: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "targetNamespace") => attr_target_namespace: optional,
+        ("", "version") => attr_version: optional,
+        ("", "finalDefault") => attr_final_default: optional,
+        ("", "blockDefault") => attr_block_default: optional,
+        ("", "attributeFormDefault") => attr_attribute_form_default: optional,
+        ("", "elementFormDefault") => attr_element_form_default: optional,
+        ("", "defaultAttributes") => attr_default_attributes: optional,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (composition, xs, Vec<Composition; min=0;>),
         (open_content, sequences, Option<AnnotatedOpenContent>),
         (sequence_schema_top_annotation, sequences, Vec<SequenceSchemaTopAnnotation; min=0;>),
@@ -871,17 +837,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Selector<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_xpath: restrictions::RestrictToken4<'input>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Selector, "http://www.w3.org/2001/XMLSchema", "selector", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpath") => attr_xpath: required,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "xpath") => attr_xpath: required,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -889,7 +855,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Sequence<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_min_occurs: Option<support::NonNegativeInteger<'input>>,
         pub attr_max_occurs: Option<xs::AllNni<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -897,10 +863,10 @@ pub mod xs {
     }
 
     impl_element!(Sequence, "http://www.w3.org/2001/XMLSchema", "sequence", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (nested_particle, xs, Vec<NestedParticle; min=0;>),
     });
@@ -908,14 +874,14 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct SimpleContent<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub choice_restriction_extension: super::enums::ChoiceRestrictionExtension<'input>,
     }
 
     impl_element!(SimpleContent, "http://www.w3.org/2001/XMLSchema", "simpleContent", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (choice_restriction_extension, enums, ChoiceRestrictionExtension),
     });
@@ -923,7 +889,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct SimpleType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_final: Option<xs::SimpleDerivationSet<'input>>,
         pub attr_name: support::NcName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -931,10 +897,10 @@ pub mod xs {
     }
 
     impl_element!(SimpleType, "http://www.w3.org/2001/XMLSchema", "simpleType", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "final") => attr_final: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "final") => attr_final: optional,
+        ("", "name") => attr_name: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (simple_derivation, xs, SimpleDerivation),
     });
@@ -942,17 +908,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct TotalDigits<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: support::PositiveInteger<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(TotalDigits, "http://www.w3.org/2001/XMLSchema", "totalDigits", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -961,16 +927,16 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Union<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_member_types: Option<lists::QNameList<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub local_simple_type: Vec<super::inline_elements::LocalSimpleType<'input>>,
     }
 
     impl_element!(Union, "http://www.w3.org/2001/XMLSchema", "union", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "memberTypes") => attr_member_types: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "memberTypes") => attr_member_types: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (local_simple_type, inline_elements, Vec<LocalSimpleType; min=0;>),
     });
@@ -978,7 +944,7 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct Unique<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_name: Option<support::NcName<'input>>,
         pub attr_ref: Option<support::QName<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -986,10 +952,10 @@ pub mod xs {
     }
 
     impl_element!(Unique, "http://www.w3.org/2001/XMLSchema", "unique", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "name") => attr_name: optional,
+        ("", "ref") => attr_ref: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (uniqueness_spec, sequences, Option<UniquenessSpec>),
     });
@@ -997,17 +963,17 @@ pub mod xs {
     #[derive(Debug, PartialEq)]
     pub struct WhiteSpace<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_value: restrictions::EnumerationPreserveReplaceCollapse<'input>,
         pub attr_fixed: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(WhiteSpace, "http://www.w3.org/2001/XMLSchema", "whiteSpace", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "value") => attr_value: required,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "value") => attr_value: required,
+        ("", "fixed") => attr_fixed: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -1292,12 +1258,12 @@ pub mod enums {
     #[derive(Debug, PartialEq)]
     pub enum ChoiceFacetAny<'input> {
         Facet(Box<super::xs::Facet<'input>>),
-        Any(Box<super::support::Any<'input>>),
+        Any(Box<super::wildcards::AnyOtherLax<'input>>),
     }
 
     impl_enum!(ChoiceFacetAny,
         impl_singleton_variant!(Facet, xs, Box<Facet>),
-        impl_singleton_variant!(Any, support, Box<Any>),
+        impl_singleton_variant!(Any, wildcards, Box<AnyOtherLax>),
     );
 
     #[derive(Debug, PartialEq)]
@@ -1375,7 +1341,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictName<'input>(pub xs::Name<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictName<'input>(pub support::Name<'input>);
 
     impl_simpletype_restriction!(RestrictName, Facets {
         min_exclusive: None,
@@ -1389,7 +1355,7 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("[\\i-[:]][\\c-[:]]*"),
+        pattern: Some(vec!["[\\i-[:]][\\c-[:]]*"]),
         assertion: None,
         explicit_timezone: None,
     });
@@ -1446,7 +1412,7 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("[\\-+]?[0-9]+"),
+        pattern: Some(vec!["[\\-+]?[0-9]+"]),
         assertion: None,
         explicit_timezone: None,
     });
@@ -1465,7 +1431,7 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("[^DT]*"),
+        pattern: Some(vec!["[^DT]*"]),
         assertion: None,
         explicit_timezone: None,
     });
@@ -1484,12 +1450,12 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("[^YM]*(T.*)?"),
+        pattern: Some(vec!["[^YM]*(T.*)?"]),
         assertion: None,
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictInt<'input>(pub xs::Int<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictInt<'input>(pub support::Int<'input>);
 
     impl_simpletype_restriction!(RestrictInt, Facets {
         min_exclusive: None,
@@ -1508,7 +1474,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictInteger<'input>(pub xs::Integer<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictInteger<'input>(pub support::Integer<'input>);
 
     impl_simpletype_restriction!(RestrictInteger, Facets {
         min_exclusive: None,
@@ -1527,7 +1493,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictInteger2<'input>(pub xs::Integer<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictInteger2<'input>(pub support::Integer<'input>);
 
     impl_simpletype_restriction!(RestrictInteger2, Facets {
         min_exclusive: None,
@@ -1546,7 +1512,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictInteger3<'input>(pub xs::Integer<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictInteger3<'input>(pub support::Integer<'input>);
 
     impl_simpletype_restriction!(RestrictInteger3, Facets {
         min_exclusive: None,
@@ -1565,7 +1531,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictLong<'input>(pub xs::Long<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictLong<'input>(pub support::Long<'input>);
 
     impl_simpletype_restriction!(RestrictLong, Facets {
         min_exclusive: None,
@@ -1641,7 +1607,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictNormalizedString<'input>(pub xs::NormalizedString<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictNormalizedString<'input>(pub support::NormalizedString<'input>);
 
     impl_simpletype_restriction!(RestrictNormalizedString, Facets {
         min_exclusive: None,
@@ -1660,7 +1626,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictShort<'input>(pub xs::Short<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictShort<'input>(pub support::Short<'input>);
 
     impl_simpletype_restriction!(RestrictShort, Facets {
         min_exclusive: None,
@@ -1712,7 +1678,7 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*"),
+        pattern: Some(vec!["[a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*"]),
         assertion: None,
         explicit_timezone: None,
     });
@@ -1731,7 +1697,7 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("\\c+"),
+        pattern: Some(vec!["\\c+"]),
         assertion: None,
         explicit_timezone: None,
     });
@@ -1750,12 +1716,12 @@ pub mod restrictions {
         max_length: None,
         enumeration: None,
         white_space: None,
-        pattern: Some("\\i\\c*"),
+        pattern: Some(vec!["\\i\\c*"]),
         assertion: None,
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedInt<'input>(pub xs::UnsignedInt<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedInt<'input>(pub support::UnsignedInt<'input>);
 
     impl_simpletype_restriction!(RestrictUnsignedInt, Facets {
         min_exclusive: None,
@@ -1774,7 +1740,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedLong<'input>(pub xs::UnsignedLong<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedLong<'input>(pub support::UnsignedLong<'input>);
 
     impl_simpletype_restriction!(RestrictUnsignedLong, Facets {
         min_exclusive: None,
@@ -1793,7 +1759,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedShort<'input>(pub xs::UnsignedShort<'input>);
+    #[derive(Debug, PartialEq)] pub struct RestrictUnsignedShort<'input>(pub support::UnsignedShort<'input>);
 
     impl_simpletype_restriction!(RestrictUnsignedShort, Facets {
         min_exclusive: None,
@@ -1812,7 +1778,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationInterleaveSuffix<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationInterleaveSuffix<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationInterleaveSuffix, Facets {
         min_exclusive: None,
@@ -1831,7 +1797,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationNoneInterleaveSuffix<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationNoneInterleaveSuffix<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationNoneInterleaveSuffix, Facets {
         min_exclusive: None,
@@ -1850,7 +1816,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationOptionalRequiredProhibited<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationOptionalRequiredProhibited<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationOptionalRequiredProhibited, Facets {
         min_exclusive: None,
@@ -1869,7 +1835,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationPreserveReplaceCollapse<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationPreserveReplaceCollapse<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationPreserveReplaceCollapse, Facets {
         min_exclusive: None,
@@ -1888,7 +1854,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationProhibitedOptionalRequired<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationProhibitedOptionalRequired<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationProhibitedOptionalRequired, Facets {
         min_exclusive: None,
@@ -1907,7 +1873,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationQualifiedUnqualified<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationQualifiedUnqualified<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationQualifiedUnqualified, Facets {
         min_exclusive: None,
@@ -1926,7 +1892,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationSkipLaxStrict<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationSkipLaxStrict<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationSkipLaxStrict, Facets {
         min_exclusive: None,
@@ -1945,7 +1911,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct EnumerationSubstitutionExtensionRestrictionListUnion<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct EnumerationSubstitutionExtensionRestrictionListUnion<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(EnumerationSubstitutionExtensionRestrictionListUnion, Facets {
         min_exclusive: None,
@@ -1964,7 +1930,7 @@ pub mod restrictions {
         explicit_timezone: None,
     });
 
-    #[derive(Debug, PartialEq)] pub struct Unbounded<'input>(pub xs::Nmtoken<'input>);
+    #[derive(Debug, PartialEq)] pub struct Unbounded<'input>(pub support::Nmtoken<'input>);
 
     impl_simpletype_restriction!(Unbounded, Facets {
         min_exclusive: None,
@@ -2301,7 +2267,7 @@ pub mod unions {
     }
 
     impl_union!(UnionQNameToken, {
-        impl_union_variant!(QName),
+        impl_union_variant!(QName, Some("http://www.w3.org/2001/XMLSchema"), "QName"),
         impl_union_variant!(Token),
     });
 
@@ -2312,7 +2278,7 @@ pub mod unions {
     }
 
     impl_union!(UnionQNameToken2, {
-        impl_union_variant!(QName),
+        impl_union_variant!(QName, Some("http://www.w3.org/2001/XMLSchema"), "QName"),
         impl_union_variant!(Token),
     });
 
@@ -2323,7 +2289,7 @@ pub mod unions {
     }
 
     impl_union!(UnionAnyUriToken, {
-        impl_union_variant!(AnyUri),
+        impl_union_variant!(AnyUri, Some("http://www.w3.org/2001/XMLSchema"), "anyURI"),
         impl_union_variant!(Token),
     });
 
@@ -2334,7 +2300,7 @@ pub mod unions {
     }
 
     impl_union!(UnionAnyUriToken2, {
-        impl_union_variant!(AnyUri),
+        impl_union_variant!(AnyUri, Some("http://www.w3.org/2001/XMLSchema"), "anyURI"),
         impl_union_variant!(Token),
     });
 
@@ -2345,7 +2311,7 @@ pub mod unions {
     }
 
     impl_union!(UnionNonNegativeIntegerNmtoken, {
-        impl_union_variant!(NonNegativeInteger),
+        impl_union_variant!(NonNegativeInteger, Some("http://www.w3.org/2001/XMLSchema"), "nonNegativeInteger"),
         impl_union_variant!(Nmtoken),
     });
 
@@ -2356,8 +2322,8 @@ pub mod unions {
     }
 
     impl_union!(UnionSpecialNamespaceListBasicNamespaceList, {
-        impl_union_variant!(SpecialNamespaceList),
-        impl_union_variant!(BasicNamespaceList),
+        impl_union_variant!(SpecialNamespaceList, Some("http://www.w3.org/2001/XMLSchema"), "specialNamespaceList"),
+        impl_union_variant!(BasicNamespaceList, Some("http://www.w3.org/2001/XMLSchema"), "basicNamespaceList"),
     });
 
     #[derive(Debug, PartialEq)]
@@ -2400,11 +2366,11 @@ pub mod sequences {
 
     #[derive(Debug, PartialEq)]
     pub struct SequenceAny<'input> {
-        pub any: super::support::Any<'input>,
+        pub any: super::wildcards::AnyLax<'input>,
     }
 
     impl_group_or_sequence!(SequenceAny,
-        (any, support, Any),
+        (any, wildcards, AnyLax),
     );
 
     #[derive(Debug, PartialEq)]
@@ -2460,13 +2426,13 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct All<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub all_model: super::xs::AllModel<'input>,
     }
 
     impl_element!(All, "http://www.w3.org/2001/XMLSchema", "all", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (all_model, xs, AllModel),
     });
 
@@ -2474,7 +2440,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct AlternativeAltType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_test: Option<support::XmlString<'input>>,
         pub attr_type: Option<support::QName<'input>>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
@@ -2483,11 +2449,11 @@ pub mod inline_elements {
     }
 
     impl_element!(AlternativeAltType, "http://www.w3.org/2001/XMLSchema", "alternative", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "test") => attr_test: optional,
-        ("http://www.w3.org/2001/XMLSchema", "type") => attr_type: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "test") => attr_test: optional,
+        ("", "type") => attr_type: optional,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (type_, enums, Option<Type>),
     });
@@ -2496,7 +2462,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct AnyWildcard<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_namespace: Option<xs::NamespaceList<'input>>,
         pub attr_not_namespace: Option<restrictions::RestrictBasicNamespaceList<'input>>,
         pub attr_process_contents: Option<restrictions::EnumerationSkipLaxStrict<'input>>,
@@ -2504,11 +2470,11 @@ pub mod inline_elements {
     }
 
     impl_element!(AnyWildcard, "http://www.w3.org/2001/XMLSchema", "any", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "namespace") => attr_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "notNamespace") => attr_not_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "processContents") => attr_process_contents: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "namespace") => attr_namespace: optional,
+        ("", "notNamespace") => attr_not_namespace: optional,
+        ("", "processContents") => attr_process_contents: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
@@ -2516,24 +2482,24 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct Assertion<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_test: Option<support::XmlString<'input>>,
         pub attr_xpath_default_namespace: Option<xs::XpathDefaultNamespace<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(Assertion, "http://www.w3.org/2001/XMLSchema", "assert", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "test") => attr_test: optional,
-        ("http://www.w3.org/2001/XMLSchema", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "test") => attr_test: optional,
+        ("", "xpathDefaultNamespace") => attr_xpath_default_namespace: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct Attribute<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_type: Option<support::QName<'input>>,
         pub attr_use: Option<restrictions::EnumerationProhibitedOptionalRequired<'input>>,
         pub attr_default: Option<support::XmlString<'input>>,
@@ -2548,17 +2514,17 @@ pub mod inline_elements {
     }
 
     impl_element!(Attribute, "http://www.w3.org/2001/XMLSchema", "attribute", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "type") => attr_type: optional,
-        ("http://www.w3.org/2001/XMLSchema", "use") => attr_use: optional,
-        ("http://www.w3.org/2001/XMLSchema", "default") => attr_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "form") => attr_form: optional,
-        ("http://www.w3.org/2001/XMLSchema", "targetNamespace") => attr_target_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "inheritable") => attr_inheritable: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "type") => attr_type: optional,
+        ("", "use") => attr_use: optional,
+        ("", "default") => attr_default: optional,
+        ("", "fixed") => attr_fixed: optional,
+        ("", "form") => attr_form: optional,
+        ("", "targetNamespace") => attr_target_namespace: optional,
+        ("", "inheritable") => attr_inheritable: optional,
+        ("", "name") => attr_name: optional,
+        ("", "ref") => attr_ref: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (local_simple_type, inline_elements, Option<LocalSimpleType>),
     });
@@ -2566,29 +2532,29 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct AttributeGroupRef<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_ref: support::QName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
     }
 
     impl_element!(AttributeGroupRef, "http://www.w3.org/2001/XMLSchema", "attributeGroup", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "ref") => attr_ref: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct ChoiceSimpleExplicitGroup<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub nested_particle: Vec<super::xs::NestedParticle<'input>>,
     }
 
     impl_element!(ChoiceSimpleExplicitGroup, "http://www.w3.org/2001/XMLSchema", "choice", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (nested_particle, xs, Vec<NestedParticle; min=0;>),
     });
@@ -2596,7 +2562,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct LocalComplexType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_mixed: Option<support::Boolean<'input>>,
         pub attr_default_attributes_apply: Option<support::Boolean<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
@@ -2604,10 +2570,10 @@ pub mod inline_elements {
     }
 
     impl_element!(LocalComplexType, "http://www.w3.org/2001/XMLSchema", "complexType", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "mixed") => attr_mixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "defaultAttributesApply") => attr_default_attributes_apply: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "mixed") => attr_mixed: optional,
+        ("", "defaultAttributesApply") => attr_default_attributes_apply: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (complex_type_model, xs, ComplexTypeModel),
     });
@@ -2615,7 +2581,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct LocalElement<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_type: Option<support::QName<'input>>,
         pub attr_default: Option<support::XmlString<'input>>,
         pub attr_fixed: Option<support::XmlString<'input>>,
@@ -2634,19 +2600,19 @@ pub mod inline_elements {
     }
 
     impl_element!(LocalElement, "http://www.w3.org/2001/XMLSchema", "element", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "type") => attr_type: optional,
-        ("http://www.w3.org/2001/XMLSchema", "default") => attr_default: optional,
-        ("http://www.w3.org/2001/XMLSchema", "fixed") => attr_fixed: optional,
-        ("http://www.w3.org/2001/XMLSchema", "nillable") => attr_nillable: optional,
-        ("http://www.w3.org/2001/XMLSchema", "block") => attr_block: optional,
-        ("http://www.w3.org/2001/XMLSchema", "form") => attr_form: optional,
-        ("http://www.w3.org/2001/XMLSchema", "targetNamespace") => attr_target_namespace: optional,
-        ("http://www.w3.org/2001/XMLSchema", "name") => attr_name: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: optional,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "type") => attr_type: optional,
+        ("", "default") => attr_default: optional,
+        ("", "fixed") => attr_fixed: optional,
+        ("", "nillable") => attr_nillable: optional,
+        ("", "block") => attr_block: optional,
+        ("", "form") => attr_form: optional,
+        ("", "targetNamespace") => attr_target_namespace: optional,
+        ("", "name") => attr_name: optional,
+        ("", "ref") => attr_ref: optional,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (type_, enums, Option<Type>),
         (alternative_alt_type, inline_elements, Vec<AlternativeAltType; min=0;>),
@@ -2656,7 +2622,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct ExtensionType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_base: support::QName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub open_content: Option<super::xs::OpenContent<'input>>,
@@ -2666,9 +2632,9 @@ pub mod inline_elements {
     }
 
     impl_element!(ExtensionType, "http://www.w3.org/2001/XMLSchema", "extension", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "base") => attr_base: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "base") => attr_base: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (open_content, xs, Option<OpenContent>),
         (type_def_particle, xs, Option<TypeDefParticle>),
@@ -2680,7 +2646,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct SimpleExtensionType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_base: support::QName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub attr_decls: super::xs::AttrDecls<'input>,
@@ -2688,9 +2654,9 @@ pub mod inline_elements {
     }
 
     impl_element!(SimpleExtensionType, "http://www.w3.org/2001/XMLSchema", "extension", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "base") => attr_base: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "base") => attr_base: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (attr_decls, xs, AttrDecls),
         (assertions, xs, Assertions),
@@ -2699,7 +2665,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct Group<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_ref: support::QName<'input>,
         pub attr_min_occurs: Option<support::NonNegativeInteger<'input>>,
         pub attr_max_occurs: Option<support::NonNegativeInteger<'input>>,
@@ -2707,18 +2673,18 @@ pub mod inline_elements {
     }
 
     impl_element!(Group, "http://www.w3.org/2001/XMLSchema", "group", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: required,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "ref") => attr_ref: required,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct GroupRef<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_ref: support::QName<'input>,
         pub attr_min_occurs: Option<support::NonNegativeInteger<'input>>,
         pub attr_max_occurs: Option<xs::AllNni<'input>>,
@@ -2726,18 +2692,18 @@ pub mod inline_elements {
     }
 
     impl_element!(GroupRef, "http://www.w3.org/2001/XMLSchema", "group", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "ref") => attr_ref: required,
-        ("http://www.w3.org/2001/XMLSchema", "minOccurs") => attr_min_occurs: optional,
-        ("http://www.w3.org/2001/XMLSchema", "maxOccurs") => attr_max_occurs: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "ref") => attr_ref: required,
+        ("", "minOccurs") => attr_min_occurs: optional,
+        ("", "maxOccurs") => attr_max_occurs: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
     });
 
     #[derive(Debug, PartialEq)]
     pub struct ComplexRestrictionType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_base: support::QName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub sequence_open_content_type_def_particle: Option<super::sequences::SequenceOpenContentTypeDefParticle<'input>>,
@@ -2746,9 +2712,9 @@ pub mod inline_elements {
     }
 
     impl_element!(ComplexRestrictionType, "http://www.w3.org/2001/XMLSchema", "restriction", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "base") => attr_base: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "base") => attr_base: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (sequence_open_content_type_def_particle, sequences, Option<SequenceOpenContentTypeDefParticle>),
         (attr_decls, xs, AttrDecls),
@@ -2758,7 +2724,7 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct SimpleRestrictionType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub attr_base: support::QName<'input>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub simple_restriction_model: Option<super::xs::SimpleRestrictionModel<'input>>,
@@ -2767,9 +2733,9 @@ pub mod inline_elements {
     }
 
     impl_element!(SimpleRestrictionType, "http://www.w3.org/2001/XMLSchema", "restriction", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-        ("http://www.w3.org/2001/XMLSchema", "base") => attr_base: required,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+        ("", "base") => attr_base: required,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (simple_restriction_model, xs, Option<SimpleRestrictionModel>),
         (attr_decls, xs, AttrDecls),
@@ -2779,14 +2745,14 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct SequenceSimpleExplicitGroup<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub nested_particle: Vec<super::xs::NestedParticle<'input>>,
     }
 
     impl_element!(SequenceSimpleExplicitGroup, "http://www.w3.org/2001/XMLSchema", "sequence", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (nested_particle, xs, Vec<NestedParticle; min=0;>),
     });
@@ -2794,15 +2760,134 @@ pub mod inline_elements {
     #[derive(Debug, PartialEq)]
     pub struct LocalSimpleType<'input> {
         pub attrs: HashMap<FullName<'input>, &'input str>,
-        pub attr_id: Option<xs::Id<'input>>,
+        pub attr_id: Option<support::Id<'input>>,
         pub annotation: Option<super::xs::Annotation<'input>>,
         pub simple_derivation: super::xs::SimpleDerivation<'input>,
     }
 
     impl_element!(LocalSimpleType, "http://www.w3.org/2001/XMLSchema", "simpleType", attributes = {
-        ("http://www.w3.org/2001/XMLSchema", "id") => attr_id: optional,
-    }, fields = {
+        ("", "id") => attr_id: optional,
+    }, any_attributes = Some(Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax }), open_content = None, mixed = false, fields = {
         (annotation, xs, Option<Annotation>),
         (simple_derivation, xs, SimpleDerivation),
     });
 }
+
+pub mod wildcards {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    pub enum KnownElement<'input> {
+        All(Box<super::xs::All<'input>>),
+        Annotation(Box<super::xs::Annotation<'input>>),
+        Any(Box<super::xs::Any<'input>>),
+        AnyAttribute(Box<super::xs::AnyAttribute<'input>>),
+        Appinfo(Box<super::xs::Appinfo<'input>>),
+        Assertion(Box<super::xs::Assertion<'input>>),
+        Attribute(Box<super::xs::Attribute<'input>>),
+        AttributeGroup(Box<super::xs::AttributeGroup<'input>>),
+        Choice(Box<super::xs::Choice<'input>>),
+        ComplexContent(Box<super::xs::ComplexContent<'input>>),
+        ComplexType(Box<super::xs::ComplexType<'input>>),
+        DefaultOpenContent(Box<super::xs::DefaultOpenContent<'input>>),
+        Documentation(Box<super::xs::Documentation<'input>>),
+        Element(Box<super::xs::Element<'input>>),
+        Enumeration(Box<super::xs::Enumeration<'input>>),
+        ExplicitTimezone(Box<super::xs::ExplicitTimezone<'input>>),
+        Field(Box<super::xs::Field<'input>>),
+        FractionDigits(Box<super::xs::FractionDigits<'input>>),
+        Group(Box<super::xs::Group<'input>>),
+        Import(Box<super::xs::Import<'input>>),
+        Include(Box<super::xs::Include<'input>>),
+        Key(Box<super::xs::Key<'input>>),
+        Keyref(Box<super::xs::Keyref<'input>>),
+        Length(Box<super::xs::Length<'input>>),
+        List(Box<super::xs::List<'input>>),
+        MaxExclusive(Box<super::xs::MaxExclusive<'input>>),
+        MaxInclusive(Box<super::xs::MaxInclusive<'input>>),
+        MaxLength(Box<super::xs::MaxLength<'input>>),
+        MinExclusive(Box<super::xs::MinExclusive<'input>>),
+        MinInclusive(Box<super::xs::MinInclusive<'input>>),
+        MinLength(Box<super::xs::MinLength<'input>>),
+        Notation(Box<super::xs::Notation<'input>>),
+        OpenContent(Box<super::xs::OpenContent<'input>>),
+        Override(Box<super::xs::Override<'input>>),
+        Pattern(Box<super::xs::Pattern<'input>>),
+        Redefine(Box<super::xs::Redefine<'input>>),
+        Restriction(Box<super::xs::Restriction<'input>>),
+        Schema(Box<super::xs::Schema<'input>>),
+        Selector(Box<super::xs::Selector<'input>>),
+        Sequence(Box<super::xs::Sequence<'input>>),
+        SimpleContent(Box<super::xs::SimpleContent<'input>>),
+        SimpleType(Box<super::xs::SimpleType<'input>>),
+        TotalDigits(Box<super::xs::TotalDigits<'input>>),
+        Union(Box<super::xs::Union<'input>>),
+        Unique(Box<super::xs::Unique<'input>>),
+        WhiteSpace(Box<super::xs::WhiteSpace<'input>>),
+    }
+
+    impl_known_elements!(KnownElement, {
+        (All, xs, All, "http://www.w3.org/2001/XMLSchema", "all"),
+        (Annotation, xs, Annotation, "http://www.w3.org/2001/XMLSchema", "annotation"),
+        (Any, xs, Any, "http://www.w3.org/2001/XMLSchema", "any"),
+        (AnyAttribute, xs, AnyAttribute, "http://www.w3.org/2001/XMLSchema", "anyAttribute"),
+        (Appinfo, xs, Appinfo, "http://www.w3.org/2001/XMLSchema", "appinfo"),
+        (Assertion, xs, Assertion, "http://www.w3.org/2001/XMLSchema", "assertion"),
+        (Attribute, xs, Attribute, "http://www.w3.org/2001/XMLSchema", "attribute"),
+        (AttributeGroup, xs, AttributeGroup, "http://www.w3.org/2001/XMLSchema", "attributeGroup"),
+        (Choice, xs, Choice, "http://www.w3.org/2001/XMLSchema", "choice"),
+        (ComplexContent, xs, ComplexContent, "http://www.w3.org/2001/XMLSchema", "complexContent"),
+        (ComplexType, xs, ComplexType, "http://www.w3.org/2001/XMLSchema", "complexType"),
+        (DefaultOpenContent, xs, DefaultOpenContent, "http://www.w3.org/2001/XMLSchema", "defaultOpenContent"),
+        (Documentation, xs, Documentation, "http://www.w3.org/2001/XMLSchema", "documentation"),
+        (Element, xs, Element, "http://www.w3.org/2001/XMLSchema", "element"),
+        (Enumeration, xs, Enumeration, "http://www.w3.org/2001/XMLSchema", "enumeration"),
+        (ExplicitTimezone, xs, ExplicitTimezone, "http://www.w3.org/2001/XMLSchema", "explicitTimezone"),
+        (Field, xs, Field, "http://www.w3.org/2001/XMLSchema", "field"),
+        (FractionDigits, xs, FractionDigits, "http://www.w3.org/2001/XMLSchema", "fractionDigits"),
+        (Group, xs, Group, "http://www.w3.org/2001/XMLSchema", "group"),
+        (Import, xs, Import, "http://www.w3.org/2001/XMLSchema", "import"),
+        (Include, xs, Include, "http://www.w3.org/2001/XMLSchema", "include"),
+        (Key, xs, Key, "http://www.w3.org/2001/XMLSchema", "key"),
+        (Keyref, xs, Keyref, "http://www.w3.org/2001/XMLSchema", "keyref"),
+        (Length, xs, Length, "http://www.w3.org/2001/XMLSchema", "length"),
+        (List, xs, List, "http://www.w3.org/2001/XMLSchema", "list"),
+        (MaxExclusive, xs, MaxExclusive, "http://www.w3.org/2001/XMLSchema", "maxExclusive"),
+        (MaxInclusive, xs, MaxInclusive, "http://www.w3.org/2001/XMLSchema", "maxInclusive"),
+        (MaxLength, xs, MaxLength, "http://www.w3.org/2001/XMLSchema", "maxLength"),
+        (MinExclusive, xs, MinExclusive, "http://www.w3.org/2001/XMLSchema", "minExclusive"),
+        (MinInclusive, xs, MinInclusive, "http://www.w3.org/2001/XMLSchema", "minInclusive"),
+        (MinLength, xs, MinLength, "http://www.w3.org/2001/XMLSchema", "minLength"),
+        (Notation, xs, Notation, "http://www.w3.org/2001/XMLSchema", "notation"),
+        (OpenContent, xs, OpenContent, "http://www.w3.org/2001/XMLSchema", "openContent"),
+        (Override, xs, Override, "http://www.w3.org/2001/XMLSchema", "override"),
+        (Pattern, xs, Pattern, "http://www.w3.org/2001/XMLSchema", "pattern"),
+        (Redefine, xs, Redefine, "http://www.w3.org/2001/XMLSchema", "redefine"),
+        (Restriction, xs, Restriction, "http://www.w3.org/2001/XMLSchema", "restriction"),
+        (Schema, xs, Schema, "http://www.w3.org/2001/XMLSchema", "schema"),
+        (Selector, xs, Selector, "http://www.w3.org/2001/XMLSchema", "selector"),
+        (Sequence, xs, Sequence, "http://www.w3.org/2001/XMLSchema", "sequence"),
+        (SimpleContent, xs, SimpleContent, "http://www.w3.org/2001/XMLSchema", "simpleContent"),
+        (SimpleType, xs, SimpleType, "http://www.w3.org/2001/XMLSchema", "simpleType"),
+        (TotalDigits, xs, TotalDigits, "http://www.w3.org/2001/XMLSchema", "totalDigits"),
+        (Union, xs, Union, "http://www.w3.org/2001/XMLSchema", "union"),
+        (Unique, xs, Unique, "http://www.w3.org/2001/XMLSchema", "unique"),
+        (WhiteSpace, xs, WhiteSpace, "http://www.w3.org/2001/XMLSchema", "whiteSpace"),
+    });
+
+    #[derive(Debug, PartialEq)]
+    pub struct AnyLax<'input>(pub support::AnyElement<'input, KnownElement<'input>>);
+
+    impl_wildcard!(AnyLax, Wildcard { namespaces: NamespaceConstraint::Any, not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax });
+
+    #[derive(Debug, PartialEq)]
+    pub struct AnyOtherLax<'input>(pub support::AnyElement<'input, KnownElement<'input>>);
+
+    impl_wildcard!(AnyOtherLax, Wildcard { namespaces: NamespaceConstraint::Not(&["http://www.w3.org/2001/XMLSchema", ""]), not_qnames: &[], not_defined: false, process_contents: ProcessContents::Lax });
+}
+
+pub const NOTATIONS: &[support::NotationDeclaration] = &[
+    support::NotationDeclaration { namespace: "http://www.w3.org/2001/XMLSchema", name: "XML", public: Some("REC-xml-19980210"), system: Some("http://www.w3.org/TR/1998/REC-xml-19980210") },
+    support::NotationDeclaration { namespace: "http://www.w3.org/2001/XMLSchema", name: "XMLSchemaStructures", public: Some("structures"), system: Some("http://www.w3.org/2000/08/XMLSchema.xsd") },
+];
@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use codegen as cg;
 use heck::{SnakeCase, CamelCase};
 
-use support::{ParseContext, Facets};
+use support::{ParseContext, Facets, ProcessContents};
 use primitives::PRIMITIVE_TYPES;
 use processor::*;
 use names::*;
@@ -31,12 +31,23 @@ fn escape_keyword(name: &str) -> String {
     }
 }
 
+/// Occurrence bounds of a `Vec` field, in the syntax of `impl_element_field!`.
+fn occurrence_bounds(min_occurs: usize, max_occurs: usize) -> String {
+    if max_occurs == usize::max_value() {
+        format!("min={};", min_occurs)
+    }
+    else {
+        format!("min={}; max={};", min_occurs, max_occurs)
+    }
+}
+
 #[derive(Debug)]
 pub struct ParserGenerator<'ast, 'input: 'ast> {
     processors: Vec<Processor<'ast, 'input>>,
     module_names: HashMap<Option<&'input str>, String>, // URI -> module name
     primitive_types: HashMap<&'static str, RichType<'input, Type<'input>>>,
     simple_restrictions: HashMap<(FullName<'input>, Facets<'input>), String>,
+    wildcard_names: HashMap<Wildcard<'input>, String>,
     renames: HashMap<String, String>,
     self_gen: bool,
 }
@@ -64,8 +75,29 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 self_gen = true;
             }
         }
+
+        let mut wildcards: Vec<_> = processors.iter().flat_map(|proc| proc.wildcards.iter()).collect();
+        wildcards.sort();
+        wildcards.dedup();
+        let mut name_gen = NameGenerator::new();
+        let mut wildcard_names = HashMap::new();
+        for wildcard in wildcards {
+            let namespaces = match wildcard.namespaces {
+                NamespaceConstraint::Any => "",
+                NamespaceConstraint::Enumeration(_) => "_namespaces",
+                NamespaceConstraint::Not(_) => "_other",
+            };
+            let process_contents = match wildcard.process_contents {
+                ProcessContents::Skip => "_skip",
+                ProcessContents::Lax => "_lax",
+                ProcessContents::Strict => "_strict",
+            };
+            let name = format!("any{}{}", namespaces, process_contents).to_camel_case();
+            wildcard_names.insert(wildcard.clone(), name_gen.gen_name(name));
+        }
+
         ParserGenerator {
-            processors, renames, module_names, primitive_types, self_gen,
+            processors, renames, module_names, primitive_types, self_gen, wildcard_names,
             simple_restrictions: HashMap::new(),
         }
     }
@@ -94,6 +126,8 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
         self.gen_elements(&mut scope);
         self.gen_inline_elements(&mut scope);
         self.gen_groups(&mut scope);
+        self.gen_wildcards(&mut scope);
+        self.gen_notations(&mut scope);
         scope
     }
 
@@ -125,18 +159,22 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 for name in names.iter() {
                     let enum_name = escape_keyword(&name.to_camel_case());
                     let enum_name = name_gen.gen_name(enum_name);
-                    self.gen_choice(module.scope(), &enum_name, choice, &Documentation::new());
+                    self.gen_choice(proc, module.scope(), &enum_name, choice, &Documentation::new());
                 }
             }
         }
     }
-    fn gen_choice(&self, scope: &mut cg::Scope, enum_name: &String, items: &Vec<RichType<'input, Type<'input>>>, doc: &Documentation<'input>) {
+    fn gen_choice(&self, proc: &Processor<'ast, 'input>, scope: &mut cg::Scope, enum_name: &String, items: &Vec<RichType<'input, Type<'input>>>, doc: &Documentation<'input>) {
+        // Alternatives are tried in order, and element particles take
+        // precedence over wildcards.
+        let mut items: Vec<_> = items.iter().enumerate().collect();
+        items.sort_by_key(|&(_, item)| proc.may_start_with_wildcard(&item.type_));
         let mut impl_code = Vec::new();
         let enum_name = self.renames.get(enum_name).unwrap_or(enum_name);
         impl_code.push(format!("impl_enum!({},", enum_name));
         {
             let enum_ = scope.new_enum(&enum_name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
-            for (i, item) in items.iter().enumerate() {
+            for (i, item) in items {
                 let mut fields = Vec::new();
                 let mut doc = doc.clone();
                 {
@@ -169,7 +207,7 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                         },
                         (_, _) => {
                             variant.tuple(&format!("Vec<super::{}::{}<'input>>", escape_keyword(&type_mod_name), escape_keyword(&type_name)));
-                            impl_code.push(format!("    impl_singleton_variant!({}, {}, Vec<{}>),", variant_name, escape_keyword(&type_mod_name), escape_keyword(&type_name)));
+                            impl_code.push(format!("    impl_singleton_variant!({}, {}, Vec<{}; {}>),", variant_name, escape_keyword(&type_mod_name), escape_keyword(&type_name), occurrence_bounds(min_occurs, max_occurs)));
                         },
                     }
                 }
@@ -186,7 +224,7 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                                 variant.named(&field_name, &format!("Option<Box<super::{}::{}<'input>> >", type_mod_name, type_name));
                             },
                             (_, _) => {
-                                impl_code.push(format!("        ({}, {}, Vec<{}>),", field_name, type_mod_name, type_name));
+                                impl_code.push(format!("        ({}, {}, Vec<{}; {}>),", field_name, type_mod_name, type_name, occurrence_bounds(min_occurs, max_occurs)));
                                 variant.named(&field_name, &format!("Vec<super::{}::{}<'input> >", type_mod_name, type_name));
                             },
                         }
@@ -219,7 +257,10 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                             let variant_name = name_from_hint(name_hint).unwrap().to_camel_case();
                             if let Some((type_mod_name, type_name)) = self.get_simple_type_name(type_) {
                                 enum_.new_variant(&variant_name).tuple(&format!("{}::{}<'input>", type_mod_name, type_name));
-                                impl_code.push(format!("    impl_union_variant!({}),", variant_name));
+                                match type_ {
+                                    SimpleType::Alias(name) => impl_code.push(format!("    impl_union_variant!({}, {:?}, {:?}),", variant_name, name.namespace(), name.local_name())),
+                                    _ => impl_code.push(format!("    impl_union_variant!({}),", variant_name)),
+                                }
                             }
                         }
                     }
@@ -367,7 +408,10 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                     None => s.push("enumeration: None,".to_string()),
                 }
                 s.push(format!("white_space: {:?},", facets.white_space));
-                s.push(format!("pattern: {:?},", facets.pattern));
+                match &facets.pattern {
+                    Some(items) => s.push(format!("pattern: Some(vec![{}]),", items.iter().map(|i| format!("{:?}", i)).collect::<Vec<_>>().join(", "))),
+                    None => s.push("pattern: None,".to_string()),
+                }
                 s.push(format!("assertion: {:?},", facets.assertion));
                 s.push(format!("explicit_timezone: {:?},", facets.explicit_timezone));
                 module.scope().raw(&format!("impl_simpletype_restriction!({}, Facets {{\n    {}\n}});", name, s.join("\n    ")));
@@ -402,7 +446,7 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 let mut module = scope.get_module_mut(&mod_name).unwrap();
                 let struct_name = name.local_name();
                 if let Type::InlineChoice(ref items) = group.type_ {
-                    self.gen_choice(module.scope(), &struct_name.to_string().to_camel_case(), items, &group.doc);
+                    self.gen_choice(proc, module.scope(), &struct_name.to_string().to_camel_case(), items, &group.doc);
                 }
                 else if let Type::InlineSequence(ref items) = group.type_ {
                     self.gen_group_or_sequence(module, struct_name, &items.iter().collect(), &group.doc);
@@ -431,13 +475,9 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                     struct_.field(&format!("pub {}", name), &format!("Option<super::{}::{}<'input>>", type_mod_name, type_name));
                     impl_code.push(format!("    ({}, {}, Option<{}>),", name, type_mod_name, type_name))
                 },
-                (_, ::std::usize::MAX) => {
-                    struct_.field(&format!("pub {}", name), &format!("Vec<super::{}::{}<'input>>", type_mod_name, type_name));
-                    impl_code.push(format!("    ({}, {}, Vec<{}; min={};>),", name, type_mod_name, type_name, min_occurs))
-                },
                 (_, _) => {
                     struct_.field(&format!("pub {}", name), &format!("Vec<super::{}::{}<'input>>", type_mod_name, type_name));
-                    impl_code.push(format!("    ({}, {}, Vec<{}; min={}; max={};>),", name, type_mod_name, type_name, min_occurs, max_occurs))
+                    impl_code.push(format!("    ({}, {}, Vec<{}; {}>),", name, type_mod_name, type_name, occurrence_bounds(min_occurs, max_occurs)))
                 },
             }
         };
@@ -489,7 +529,12 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 }
 
                 let tag_name = FullName::new(*namespace, tag_name);
-                self.gen_element(module, &struct_names[0], &tag_name, attrs, element, doc);
+                if self.is_abstract_type(element) {
+                    self.gen_abstract_element(module, &struct_names[0], doc);
+                }
+                else {
+                    self.gen_element(module, "inline_elements", &struct_names[0], &tag_name, attrs, element, doc);
+                }
             }
         }
     }
@@ -502,76 +547,287 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
             for (&name, element) in elements {
                 let mod_name = self.get_module_name(name);
                 let mut module = scope.get_module_mut(&mod_name).expect(&mod_name);
-                let head_local_name = format!("{}_head", name.local_name());
-                let mut substitutions = Vec::new();
-                substitutions.push(FullName::new(name.namespace(), &head_local_name));
-                for proc in &self.processors {
-                    if let Some(members) = proc.substitution_groups.get(&name) {
-                        substitutions.extend(members);
+                if self.is_head(&name) {
+                    // Reference sites accept any member of the substitution
+                    // group, which may appear in instances.
+                    let mut members = Vec::new();
+                    self.get_substitution_group(&name, &mut members);
+                    let mut substitutions = Vec::new();
+                    substitutions.push(name);
+                    substitutions.extend(members.into_iter().filter(|member| !self.is_substitution_blocked(&name, member)));
+                    substitutions.retain(|substitution| !self.is_abstract_declaration(substitution));
+                    let enum_name = escape_keyword(&name.local_name().to_camel_case());
+                    if substitutions.is_empty() {
+                        // Nothing can appear at reference sites; alias the
+                        // abstract head, which never parses.
+                        module.scope().raw(&format!("pub type {}<'input> = {}<'input>;", enum_name, self.get_element_struct_name(name)));
+                    }
+                    else {
+                        self.gen_substitution_enum(module.scope(), &enum_name, &name, &substitutions);
                     }
                 }
-                if substitutions.len() > 1 {
-                    let enum_name = escape_keyword(&name.local_name().to_camel_case());
-                    self.gen_substitution_enum(module.scope(), &enum_name, &substitutions);
-                    let struct_name = escape_keyword(&head_local_name.to_camel_case());
-                    self.gen_element(module, &struct_name, &name, &element.attrs, &element.type_, &element.doc);
+                let struct_name = self.get_element_struct_name(name);
+                if self.is_abstract_element(&name) || self.is_abstract_type(&element.type_) {
+                    self.gen_abstract_element(module, &struct_name, &element.doc);
                 }
                 else {
-                    let struct_name = escape_keyword(&name.local_name().to_camel_case());
-                    self.gen_element(module, &struct_name, &name, &element.attrs, &element.type_, &element.doc);
+                    self.gen_element(module, &mod_name, &struct_name, &name, &element.attrs, &element.type_, &element.doc);
+                }
+            }
+        }
+    }
+
+    fn gen_wildcards(&self, scope: &mut cg::Scope) {
+        if self.wildcard_names.is_empty() {
+            return;
+        }
+        let module = scope.new_module("wildcards");
+        module.vis("pub");
+        module.scope().raw("#[allow(unused_imports)]\nuse super::*;");
+
+        // Top-level elements, which lax and strict wildcards parse as themselves.
+        let mut elements: Vec<_> = self.processors.iter()
+            .flat_map(|proc| proc.elements.iter())
+            .filter(|&(name, element)| !self.is_abstract_element(name) && !self.is_abstract_type(&element.type_))
+            .map(|(name, _)| name)
+            .collect();
+        elements.sort();
+        if elements.is_empty() {
+            module.scope().raw("pub type KnownElement<'input> = support::NoElement<'input>;");
+        }
+        else {
+            let mut impl_code = Vec::new();
+            impl_code.push("impl_known_elements!(KnownElement, {".to_string());
+            let mut name_gen = NameGenerator::new();
+            {
+                let enum_ = module.scope().new_enum("KnownElement").vis("pub").derive("Debug").derive("PartialEq").generic("'input");
+                for &name in elements {
+                    let variant_name = escape_keyword(&name_gen.gen_name(name.local_name().to_camel_case()));
+                    let type_mod_name = escape_keyword(&self.get_module_name(name).to_snake_case());
+                    let type_name = self.get_element_struct_name(name);
+                    enum_.new_variant(&variant_name).tuple(&format!("Box<super::{}::{}<'input>>", type_mod_name, type_name));
+                    impl_code.push(format!("    ({}, {}, {}, {:?}, {:?}),", variant_name, type_mod_name, type_name, name.namespace().unwrap_or(""), name.local_name()));
+                }
+            }
+            impl_code.push("});".to_string());
+            module.scope().raw(&impl_code.join("\n"));
+        }
+
+        let mut wildcards: Vec<_> = self.wildcard_names.iter().collect();
+        wildcards.sort_by_key(|&(_, name)| name);
+        for (wildcard, name) in wildcards {
+            {
+                let struct_ = module.new_struct(name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
+                struct_.tuple_field("pub support::AnyElement<'input, KnownElement<'input>>");
+            }
+            module.scope().raw(&format!("impl_wildcard!({}, {});", name, self.gen_wildcard(wildcard)));
+        }
+    }
+
+    /// Generates the table of the notations declared by the schemas, for
+    /// parse contexts to resolve NOTATION values against.
+    fn gen_notations(&self, scope: &mut cg::Scope) {
+        let mut notations: Vec<_> = self.processors.iter().flat_map(|proc| proc.notations.iter()).collect();
+        notations.sort();
+        let mut code = Vec::new();
+        code.push("pub const NOTATIONS: &[support::NotationDeclaration] = &[".to_string());
+        for (name, (public, system)) in notations {
+            code.push(format!("    support::NotationDeclaration {{ namespace: {:?}, name: {:?}, public: {:?}, system: {:?} }},",
+                name.namespace().unwrap_or(""), name.local_name(), public, system));
+        }
+        code.push("];".to_string());
+        scope.raw(&code.join("\n"));
+    }
+
+    /// Returns an expression building the `support::Wildcard` matching this one.
+    fn gen_wildcard(&self, wildcard: &Wildcard<'input>) -> String {
+        let format_namespaces = |namespaces: &Vec<Option<&'input str>>| {
+            namespaces.iter().map(|ns| format!("{:?}", ns.unwrap_or(""))).collect::<Vec<_>>().join(", ")
+        };
+        let namespaces = match wildcard.namespaces {
+            NamespaceConstraint::Any => "NamespaceConstraint::Any".to_string(),
+            NamespaceConstraint::Enumeration(ref namespaces) =>
+                format!("NamespaceConstraint::Enumeration(&[{}])", format_namespaces(namespaces)),
+            NamespaceConstraint::Not(ref namespaces) =>
+                format!("NamespaceConstraint::Not(&[{}])", format_namespaces(namespaces)),
+        };
+        let not_qnames: Vec<_> = wildcard.not_qnames.iter().map(|name| {
+            format!("({:?}, {:?})", name.namespace().unwrap_or(""), name.local_name())
+        }).collect();
+        format!("Wildcard {{ namespaces: {}, not_qnames: &[{}], not_defined: {:?}, process_contents: ProcessContents::{:?} }}",
+            namespaces, not_qnames.join(", "), wildcard.not_defined, wildcard.process_contents)
+    }
+
+    /// Returns the attribute wildcard of these attributes, including the ones
+    /// of the attribute groups they reference.
+    fn get_attribute_wildcard(&self, attrs: &Attrs<'input>) -> Option<Wildcard<'input>> {
+        let mut wildcard = attrs.any_attributes.clone();
+        for group_name in &attrs.group_refs {
+            for processor in self.processors.iter() {
+                if let Some(group_attrs) = processor.attribute_groups.get(group_name) {
+                    wildcard = match (wildcard, self.get_attribute_wildcard(group_attrs)) {
+                        (Some(w1), Some(w2)) => Some(w1.union(&w2)),
+                        (w1, w2) => w1.or(w2),
+                    };
+                    break;
+                }
+            }
+        }
+        wildcard
+    }
+
+    fn is_abstract_element(&self, name: &FullName<'input>) -> bool {
+        self.processors.iter().any(|proc| proc.abstract_elements.contains(name))
+    }
+
+    /// Whether this top-level element can never appear in instances, either
+    /// because it is abstract or because its type is.
+    fn is_abstract_declaration(&self, name: &FullName<'input>) -> bool {
+        self.is_abstract_element(name) || self.processors.iter()
+            .filter_map(|proc| proc.elements.get(name))
+            .any(|element| self.is_abstract_type(&element.type_))
+    }
+
+    fn is_head(&self, name: &FullName<'input>) -> bool {
+        self.processors.iter().any(|proc| proc.substitution_groups.contains_key(name))
+    }
+
+    /// Name of the structure generated for a top-level element. The
+    /// element's own name is used by the substitution enum if it is the head
+    /// of a substitution group.
+    fn get_element_struct_name(&self, name: FullName<'input>) -> String {
+        if self.is_head(&name) {
+            escape_keyword(&format!("{}_head", name.local_name()).to_camel_case())
+        }
+        else {
+            escape_keyword(&name.local_name().to_camel_case())
+        }
+    }
+
+    /// Collects the members of the substitution group of `head`, including
+    /// the members of the groups of its members.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-equiv-class
+    fn get_substitution_group(&self, head: &FullName<'input>, members: &mut Vec<FullName<'input>>) {
+        for proc in &self.processors {
+            if let Some(direct_members) = proc.substitution_groups.get(head) {
+                for member in direct_members {
+                    if !members.contains(member) {
+                        members.push(*member);
+                        self.get_substitution_group(member, members);
+                    }
                 }
             }
         }
     }
 
-    fn gen_substitution_enum(&self, scope: &mut cg::Scope, enum_name: &str, substitutions: &Vec<FullName<'input>>) {
+    /// Whether elements of this type can only appear in instances through
+    /// xsi:type, which generated parsers refuse.
+    fn is_abstract_type(&self, type_: &Type<'input>) -> bool {
+        match type_ {
+            Type::Alias(name) => self.processors.iter().any(|proc| proc.abstract_types.contains(name)),
+            _ => false,
+        }
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-equiv-derived-ok-rec
+    fn is_substitution_blocked(&self, head: &FullName<'input>, member: &FullName<'input>) -> bool {
+        let head_proc = match self.processors.iter().find(|proc| proc.elements.contains_key(head)) {
+            Some(proc) => proc,
+            None => return false,
+        };
+        let mut blocked = head_proc.element_blocks.get(head).cloned().unwrap_or_default();
+        if blocked.substitution {
+            return true;
+        }
+        let head_type = match head_proc.element_type_name(head) {
+            Some(head_type) => head_type,
+            None => return false,
+        };
+        for proc in &self.processors {
+            if let Some(type_block) = proc.type_blocks.get(&head_type) {
+                blocked = blocked.union(type_block);
+            }
+        }
+        let member_type = self.processors.iter().filter_map(|proc| proc.element_type_name(member)).next();
+        let derivations: Vec<_> = self.processors.iter().flat_map(|proc| proc.derivations.iter().cloned()).collect();
+        match member_type.and_then(|member_type| derivation_methods(&derivations, &member_type, &head_type)) {
+            Some(methods) => methods.intersects(&blocked),
+            None => false,
+        }
+    }
+
+    fn gen_abstract_element(&self, module: &mut cg::Module, struct_name: &str, doc: &Documentation<'input>) {
+        {
+            let struct_ = module.new_struct(&struct_name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
+            struct_.tuple_field("pub PhantomData<&'input ()>");
+            struct_.doc(&doc.to_string());
+        }
+        module.scope().raw(&format!("impl_abstract_element!({});", struct_name));
+    }
+
+    fn gen_substitution_enum(&self, scope: &mut cg::Scope, enum_name: &str, head: &FullName<'input>, substitutions: &Vec<FullName<'input>>) {
         let mut impl_code = Vec::new();
         impl_code.push(format!("impl_enum!({},", enum_name));
         let mut name_gen = NameGenerator::new();
         {
             let enum_ = scope.new_enum(&enum_name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
             for &substitution in substitutions {
-                let variant_name = escape_keyword(&name_gen.gen_name(substitution.local_name().to_camel_case()));
                 let type_mod_name = escape_keyword(&self.get_module_name(substitution).to_snake_case());
-                let type_name = escape_keyword(&substitution.local_name().to_camel_case());
+                let type_name = self.get_element_struct_name(substitution);
+                let variant_name = if substitution == *head {
+                    format!("{}_head", substitution.local_name())
+                }
+                else {
+                    substitution.local_name().to_string()
+                };
+                let variant_name = escape_keyword(&name_gen.gen_name(variant_name.to_camel_case()));
                 let mut variant = enum_.new_variant(&variant_name);
-                variant.tuple(&format!("Box<super::{}::{}<'input>>", escape_keyword(&type_mod_name), escape_keyword(&type_name)));
-                impl_code.push(format!("    impl_singleton_variant!({}, {}, Box<{}>),", variant_name, escape_keyword(&type_mod_name), escape_keyword(&type_name)));
+                variant.tuple(&format!("Box<super::{}::{}<'input>>", type_mod_name, type_name));
+                impl_code.push(format!("    impl_singleton_variant!({}, {}, Box<{}>),", variant_name, type_mod_name, type_name));
             }
             impl_code.push(");".to_string());
         }
         scope.raw(&impl_code.join("\n"));
     }
 
+    fn gen_attr(&self, struct_: &mut cg::Struct, impl_code: &mut Vec<String>, name_gen: &mut NameGenerator, attr_name: &FullName<'input>, use_: &AttrUse, attr_type: &Option<SimpleType<'input>>, seen_attrs: &mut HashMap<FullName<'input>, AttrUse>, generated_attrs: &mut HashSet<FullName<'input>>, inherited: bool) {
+        if generated_attrs.contains(attr_name) {
+            return;
+        }
+        let default_type = SimpleType::Primitive(SCHEMA_URI, "AnySimpleType");
+        let type_ = attr_type.as_ref().unwrap_or(&default_type);
+        let (type_mod_name, type_name) = self.get_simple_type_name(&type_).unwrap();
+        let use_ = if inherited {
+            *seen_attrs.get(attr_name).unwrap_or(use_)
+        }
+        else {
+            *use_
+        };
+        seen_attrs.insert(attr_name.clone(), use_);
+        generated_attrs.insert(attr_name.clone());
+        match use_ {
+            AttrUse::Optional => {
+                let field_name = name_gen.gen_name(format!("attr_{}", attr_name.local_name()).to_snake_case());
+                struct_.field(&format!("pub {}", field_name), &format!("Option<{}::{}<'input>>", type_mod_name, type_name));
+                impl_code.push(format!("    ({:?}, {:?}) => {}: optional,", attr_name.namespace().unwrap_or(""), attr_name.local_name(), field_name));
+            },
+            AttrUse::Required => {
+                let field_name = name_gen.gen_name(format!("attr_{}", attr_name.local_name()).to_snake_case());
+                struct_.field(&format!("pub {}", field_name), &format!("{}::{}<'input>", type_mod_name, type_name));
+                impl_code.push(format!("    ({:?}, {:?}) => {}: required,", attr_name.namespace().unwrap_or(""), attr_name.local_name(), field_name));
+            },
+            AttrUse::Prohibited => (),
+        }
+    }
+
     fn gen_attrs(&self, struct_: &mut cg::Struct, impl_code: &mut Vec<String>, name_gen: &mut NameGenerator, attrs: &Attrs<'input>, seen_attrs: &mut HashMap<FullName<'input>, AttrUse>, generated_attrs: &mut HashSet<FullName<'input>>, inherited: bool) {
         for (attr_name, use_, attr_type) in &attrs.named {
-            if generated_attrs.contains(attr_name) {
-                continue;
-            }
-            let default_type = SimpleType::Primitive(SCHEMA_URI, "AnySimpleType");
-            let type_ = attr_type.as_ref().unwrap_or(&default_type);
-            let (type_mod_name, type_name) = self.get_simple_type_name(&type_).unwrap();
-            let use_ = if inherited {
-                *seen_attrs.get(attr_name).unwrap_or(use_)
-            }
-            else {
-                *use_
-            };
-            seen_attrs.insert(attr_name.clone(), use_);
-            generated_attrs.insert(attr_name.clone());
-            match use_ {
-                AttrUse::Optional => {
-                    let field_name = name_gen.gen_name(format!("attr_{}", attr_name.local_name()).to_snake_case());
-                    struct_.field(&format!("pub {}", field_name), &format!("Option<{}::{}<'input>>", type_mod_name, type_name));
-                    impl_code.push(format!("    ({:?}, {:?}) => {}: optional,", attr_name.namespace().unwrap_or(""), attr_name.local_name(), field_name));
-                },
-                AttrUse::Required => {
-                    let field_name = name_gen.gen_name(format!("attr_{}", attr_name.local_name()).to_snake_case());
-                    struct_.field(&format!("pub {}", field_name), &format!("{}::{}<'input>", type_mod_name, type_name));
-                    impl_code.push(format!("    ({:?}, {:?}) => {}: required,", attr_name.namespace().unwrap_or(""), attr_name.local_name(), field_name));
-                },
-                AttrUse::Prohibited => (),
-            }
+            self.gen_attr(struct_, impl_code, name_gen, attr_name, use_, attr_type, seen_attrs, generated_attrs, inherited);
+        }
+        for (_, use_, ref_) in &attrs.refs {
+            let attr_type = self.get_attribute_type(ref_);
+            self.gen_attr(struct_, impl_code, name_gen, ref_, use_, &attr_type, seen_attrs, generated_attrs, inherited);
         }
         for group_name in &attrs.group_refs {
             let mut found = false;
@@ -588,10 +844,11 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
         }
     }
 
-    fn gen_element(&self, module: &mut cg::Module, struct_name: &str, tag_name: &FullName<'input>, attrs: &Attrs<'input>, type_: &Type<'input>, doc: &Documentation<'input>) {
+    fn gen_element(&self, module: &mut cg::Module, mod_name: &str, struct_name: &str, tag_name: &FullName<'input>, attrs: &Attrs<'input>, type_: &Type<'input>, doc: &Documentation<'input>) {
         let mut impl_code = Vec::new();
         impl_code.push(format!("impl_element!({}, {:?}, \"{}\", attributes = {{",
             struct_name, tag_name.namespace().unwrap_or(""), tag_name.local_name()));
+        let mixed = self.is_mixed(type_);
         {
             let struct_ = module.new_struct(&struct_name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
             let mut empty_struct = false;
@@ -608,12 +865,91 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 let doc_writer = &mut |_| ();
                 self.write_type_in_struct_def(field_writer, &mut Some(doc_writer), &type_);
             }
-            impl_code.push(format!("}}, fields = {{"));
-            self.gen_fields(&mut empty_struct, struct_, &mut impl_code, &mut doc, &mut name_gen, type_);
+            let any_attributes = match self.get_attribute_wildcard(&attrs) {
+                None => "None".to_string(),
+                Some(wildcard) => format!("Some({})", self.gen_wildcard(&wildcard)),
+            };
+            let open_content = self.get_open_content(type_);
+            let open_content_expr = match open_content {
+                Some(OpenContent { mode: OpenContentMode::Interleave, wildcard }) => {
+                    let mut siblings = Vec::new();
+                    self.get_content_model_elements(type_, &mut siblings);
+                    siblings.sort();
+                    siblings.dedup();
+                    let siblings: Vec<_> = siblings.iter().map(|name| {
+                        format!("({:?}, {:?})", name.namespace().unwrap_or(""), name.local_name())
+                    }).collect();
+                    format!("Some(OpenContent {{ wildcard: {}, siblings: &[{}] }})", self.gen_wildcard(wildcard), siblings.join(", "))
+                },
+                Some(OpenContent { mode: OpenContentMode::Suffix, .. }) | None => "None".to_string(),
+            };
+            impl_code.push(format!("}}, any_attributes = {}, open_content = {}, mixed = {:?}, fields = {{", any_attributes, open_content_expr, mixed));
+            // The children of mixed content are only exposed interleaved with
+            // the character data, so the fields of the content model only
+            // check it, and are not part of the structure.
+            let mut content_struct = cg::Struct::new(struct_name);
+            let mut content_code = Vec::new();
+            {
+                let (content_struct, content_code) = if mixed { (&mut content_struct, &mut content_code) } else { (&mut *struct_, &mut impl_code) };
+                self.gen_fields(&mut empty_struct, content_struct, content_code, &mut doc, &mut name_gen, type_);
+                if let Some(open_content) = open_content {
+                    let name = escape_keyword(&name_gen.gen_name("open_content".to_string()));
+                    let type_name = self.wildcard_names.get(&open_content.wildcard).expect(&format!("Unknown wildcard: {:?}", open_content.wildcard));
+                    content_struct.field(&format!("pub {}", name), &format!("Vec<super::wildcards::{}<'input>>", type_name));
+                    match open_content.mode {
+                        OpenContentMode::Interleave =>
+                            content_code.push(format!("    ({}, wildcards, OpenContent<{}>),", name, type_name)),
+                        OpenContentMode::Suffix =>
+                            content_code.push(format!("    ({}, wildcards, Vec<{}; min=0;>),", name, type_name)),
+                    }
+                }
+            }
+            if mixed {
+                let name = escape_keyword(&name_gen.gen_name("mixed".to_string()));
+                let type_mod_name = escape_keyword(&mod_name.to_snake_case());
+                struct_.field(&format!("pub {}", name), &format!("Vec<support::MixedItem<'input, super::{}::{}Child<'input>>>", type_mod_name, struct_name));
+                impl_code.push(format!("    ({}, {}, Mixed<{}Child> {{", name, type_mod_name, struct_name));
+                impl_code.extend(content_code.into_iter().map(|line| format!("    {}", line)));
+                impl_code.push("    }),".to_string());
+            }
             struct_.doc(&doc.to_string());
         }
         impl_code.push(format!("}});"));
         module.scope().raw(&impl_code.join("\n"));
+        if mixed {
+            self.gen_mixed_children(module, &format!("{}Child", struct_name), type_);
+        }
+    }
+
+    /// Generates the enum of the elements which may appear in the content
+    /// of a mixed content type, so they can be interleaved with its
+    /// character data.
+    fn gen_mixed_children(&self, module: &mut cg::Module, enum_name: &str, type_: &Type<'input>) {
+        let mut particles = Vec::new();
+        self.get_element_particles(type_, &mut particles);
+        let mut seen = HashSet::new();
+        particles.retain(|&(name, _)| seen.insert(name));
+        if particles.is_empty() {
+            module.scope().raw(&format!("pub type {}<'input> = support::NoElement<'input>;", enum_name));
+            return;
+        }
+        let mut impl_code = Vec::new();
+        impl_code.push(format!("impl_known_elements!({}, {{", enum_name));
+        let mut name_gen = NameGenerator::new();
+        {
+            let enum_ = module.scope().new_enum(enum_name).vis("pub").derive("Debug").derive("PartialEq").generic("'input");
+            for (name, struct_name) in particles {
+                let variant_name = escape_keyword(&name_gen.gen_name(name.local_name().to_camel_case()));
+                let (type_mod_name, type_name) = match struct_name {
+                    Some(struct_name) => ("inline_elements".to_string(), escape_keyword(&struct_name.to_camel_case())),
+                    None => (escape_keyword(&self.get_module_name(name).to_snake_case()), self.get_element_struct_name(name)),
+                };
+                enum_.new_variant(&variant_name).tuple(&format!("Box<super::{}::{}<'input>>", type_mod_name, type_name));
+                impl_code.push(format!("    ({}, {}, {}, {:?}, {:?}),", variant_name, type_mod_name, type_name, name.namespace().unwrap_or(""), name.local_name()));
+            }
+        }
+        impl_code.push("});".to_string());
+        module.scope().raw(&impl_code.join("\n"));
     }
 
     fn get_type(&self, name: &FullName<'input>) -> &RichType<'input, Type<'input>> {
@@ -636,6 +972,163 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
         type_
     }
 
+    /// Returns the open content of a complex type, which is inherited by
+    /// extensions that do not have one.
+    fn get_open_content<'a>(&'a self, type_: &'a Type<'input>) -> Option<&'a OpenContent<'input>> {
+        match type_ {
+            Type::OpenContent(open_content, _) => Some(open_content),
+            Type::Mixed(inner) => self.get_open_content(&inner.type_),
+            Type::Alias(name) => self.get_open_content(&self.get_type(name).type_),
+            Type::Extension(base, _) => self.get_open_content(&self.get_type(base).type_),
+            _ => None,
+        }
+    }
+
+    /// Whether a complex type's content is mixed. Extensions have the same
+    /// content type as their base.
+    fn is_mixed(&self, type_: &Type<'input>) -> bool {
+        match type_ {
+            Type::Mixed(_) => true,
+            Type::Alias(name) => self.is_mixed(&self.get_type(name).type_),
+            Type::Extension(base, _) => self.is_mixed(&self.get_type(base).type_),
+            Type::OpenContent(_, inner) => self.is_mixed(&inner.type_),
+            _ => false,
+        }
+    }
+
+    /// Returns the particle of a complex type's content model, following
+    /// type names and derivations which do not change it.
+    fn get_content<'a>(&'a self, type_: &'a Type<'input>) -> &'a Type<'input> {
+        match type_ {
+            Type::Alias(name) => self.get_content(&self.get_type(name).type_),
+            Type::Extension(base, ext_type) => {
+                match self.get_content(&self.get_type(base).type_) {
+                    Type::Empty => self.get_content(&ext_type.type_),
+                    _ => type_,
+                }
+            },
+            Type::Restriction(_, inner) |
+            Type::OpenContent(_, inner) |
+            Type::Mixed(inner) => self.get_content(&inner.type_),
+            _ => type_,
+        }
+    }
+
+    /// Whether two particles are the same, up to the names of the
+    /// structures generated for them.
+    fn same_content(&self, type1: &Type<'input>, type2: &Type<'input>) -> bool {
+        match (type1, type2) {
+            (Type::Sequence(min1, max1, name1), Type::Sequence(min2, max2, name2)) => {
+                (min1, max1) == (min2, max2) && self.processors.iter().any(|proc| {
+                    proc.sequences.values().any(|(names, _)| names.contains(name1) && names.contains(name2))
+                })
+            },
+            (Type::Choice(min1, max1, name1), Type::Choice(min2, max2, name2)) => {
+                (min1, max1) == (min2, max2) && self.processors.iter().any(|proc| {
+                    proc.choices.values().any(|names| names.contains(name1) && names.contains(name2))
+                })
+            },
+            (Type::Element(min1, max1, name1), Type::Element(min2, max2, name2)) => {
+                (min1, max1) == (min2, max2) && self.processors.iter().any(|proc| {
+                    proc.inline_elements.values().any(|(names, _)| names.contains(name1) && names.contains(name2))
+                })
+            },
+            (Type::InlineSequence(items1), Type::InlineSequence(items2)) |
+            (Type::InlineChoice(items1), Type::InlineChoice(items2)) => {
+                items1.len() == items2.len() &&
+                    items1.iter().zip(items2.iter()).all(|(item1, item2)| self.same_content(&item1.type_, &item2.type_))
+            },
+            _ => type1 == type2,
+        }
+    }
+
+    /// Collects the names of the elements which may appear in a content model.
+    fn get_content_model_elements(&self, type_: &Type<'input>, names: &mut Vec<FullName<'input>>) {
+        let mut particles = Vec::new();
+        self.get_element_particles(type_, &mut particles);
+        names.extend(particles.into_iter().map(|(name, _)| name));
+    }
+
+    /// Collects the elements which may appear in a content model, with the
+    /// name of the structure generated for local ones.
+    fn get_element_particles(&self, type_: &Type<'input>, particles: &mut Vec<(FullName<'input>, Option<String>)>) {
+        match type_ {
+            Type::Alias(name) => self.get_element_particles(&self.get_type(name).type_, particles),
+            Type::Extension(base, ext_type) => {
+                self.get_element_particles(&self.get_type(base).type_, particles);
+                self.get_element_particles(&ext_type.type_, particles);
+            },
+            Type::Restriction(_, ext_type) |
+            Type::OpenContent(_, ext_type) |
+            Type::Mixed(ext_type) => self.get_element_particles(&ext_type.type_, particles),
+            Type::InlineSequence(items) |
+            Type::InlineChoice(items) => {
+                for item in items {
+                    self.get_element_particles(&item.type_, particles);
+                }
+            },
+            Type::ElementRef(_, _, name) => {
+                particles.push((*name, None));
+                let mut members = Vec::new();
+                self.get_substitution_group(name, &mut members);
+                particles.extend(members.into_iter().map(|member| (member, None)));
+            },
+            Type::Element(_, _, struct_name) => {
+                for proc in &self.processors {
+                    for ((namespace, local_name, _, _), (struct_names, _)) in proc.inline_elements.iter() {
+                        if struct_names.contains(struct_name) {
+                            particles.push((FullName::new(*namespace, *local_name), Some(struct_name.clone())));
+                        }
+                    }
+                }
+            },
+            Type::Group(_, _, name) => {
+                for proc in &self.processors {
+                    if let Some(group) = proc.groups.get(name) {
+                        self.get_element_particles(&group.type_, particles);
+                    }
+                }
+            },
+            Type::Sequence(_, _, struct_name) => {
+                for proc in &self.processors {
+                    for (items, (struct_names, _)) in proc.sequences.iter() {
+                        if struct_names.contains(struct_name) {
+                            for item in items {
+                                self.get_element_particles(&item.type_, particles);
+                            }
+                        }
+                    }
+                }
+            },
+            Type::Choice(_, _, struct_name) => {
+                for proc in &self.processors {
+                    for (items, struct_names) in proc.choices.iter() {
+                        if struct_names.contains(struct_name) {
+                            for item in items {
+                                self.get_element_particles(&item.type_, particles);
+                            }
+                        }
+                    }
+                }
+            },
+            Type::Any(_) | Type::Empty | Type::Simple(_) => (),
+        }
+    }
+
+    fn get_attribute_type(&self, name: &FullName<'input>) -> Option<SimpleType<'input>> {
+        for proc in &self.processors {
+            if proc.target_namespace != name.namespace() {
+                continue;
+            }
+            if let Some(type_) = proc.attributes.get(name) {
+                return type_.clone();
+            }
+        }
+        // Declared in a schema we were not given (eg. xml:lang); accept
+        // any value.
+        None
+    }
+
     fn write_type_in_struct_def<'a, F, H>(&'a self,
             field_writer: &mut F,
             doc_writer: &mut Option<&mut H>,
@@ -682,13 +1175,37 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                 if let Some(ref mut f) = doc_writer {
                     f(&ext_type.doc);
                 }
-                let base_type = &self.get_type(base);
-                // TODO: do something with the base's type
-                self.write_type_in_struct_def(field_writer, doc_writer, &ext_type.type_);
+                let base_content = self.get_content(&self.get_type(base).type_);
+                let content = self.get_content(&ext_type.type_);
+                if self.same_content(base_content, content) {
+                    // The restriction does not change the content model,
+                    // so its fields are the base's.
+                    self.write_type_in_struct_def(field_writer, doc_writer, base_content);
+                }
+                else {
+                    match (base_content, content) {
+                        (Type::InlineSequence(base_items), Type::InlineSequence(items)) if base_items.len() == items.len() => {
+                            for (base_item, item) in base_items.iter().zip(items.iter()) {
+                                let item = if self.same_content(&base_item.type_, &item.type_) { base_item } else { item };
+                                self.write_type_in_struct_def(field_writer, &mut doc_non_writer, &item.type_);
+                            }
+                        },
+                        _ => self.write_type_in_struct_def(field_writer, doc_writer, content),
+                    }
+                }
             },
-            Type::Empty => (), // TODO ?
-            Type::Any => {
-                field_writer("any".to_string(), "support".to_string(), 1, 1, "Any".to_string())
+            Type::OpenContent(_, inner) => {
+                // The open content's own field is written by gen_element.
+                self.write_type_in_struct_def(field_writer, doc_writer, &inner.type_);
+            },
+            Type::Mixed(inner) => {
+                // So is the interleaved content of mixed types.
+                self.write_type_in_struct_def(field_writer, doc_writer, &inner.type_);
+            },
+            Type::Empty => (), // Empty content; only attributes, written by gen_element
+            Type::Any(wildcard) => {
+                let type_name = self.wildcard_names.get(wildcard).expect(&format!("Unknown wildcard: {:?}", wildcard));
+                field_writer("any".to_string(), "wildcards".to_string(), 1, 1, type_name.to_string())
             },
             Type::Simple(type_) => {
                 let (type_mod_name, type_name) = self.get_simple_type_name(&type_).unwrap();
@@ -712,7 +1229,7 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
             Type::ElementRef(_, _, _) |
             Type::Choice(_, _, _) |
             Type::Empty |
-            Type::Any => {
+            Type::Any(_) => {
                 own_attrs.clone()
             }
             Type::Extension(base, ext_type) => {
@@ -730,6 +1247,11 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
             Type::Simple(type2) => {
                 own_attrs.clone()
             }
+            Type::OpenContent(_, inner) |
+            Type::Mixed(inner) => {
+                let inner_attrs = self.compute_attrs(&inner.type_, &inner.attrs);
+                self.extend_attrs(&inner_attrs, own_attrs)
+            }
             _ => unimplemented!("writing {:?}", type_),
         }
     }
@@ -774,7 +1296,11 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
                         refs.push((Some(name.clone()), *attr_use, ref_.clone()));
                     }
                 },
-                None => (), // TODO
+                None => {
+                    if !refs.iter().any(|(_, _, r)| r == ref_) {
+                        refs.push((None, *attr_use, ref_.clone()));
+                    }
+                },
             }
         }
 
@@ -786,7 +1312,11 @@ impl<'ast, 'input: 'ast> ParserGenerator<'ast, 'input> {
             }
         }
 
-        let res = Attrs { named, refs, group_refs, any_attributes: other.any_attributes };
+        let any_attributes = match (&base.any_attributes, &other.any_attributes) {
+            (Some(w1), Some(w2)) => Some(w1.union(w2)),
+            (w1, w2) => w1.clone().or(w2.clone()),
+        };
+        let res = Attrs { named, refs, group_refs, any_attributes };
         res
     }
 
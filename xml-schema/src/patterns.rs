@@ -0,0 +1,305 @@
+//! Regular expressions of `pattern` facets, which are translated to the
+//! syntax of the `regex` crate.
+//!
+//! https://www.w3.org/TR/xmlschema11-2/#regexs
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use regex::Regex;
+
+/// https://www.w3.org/TR/xml11/#NT-NameStartChar
+const NAME_START_CHARS: &'static str = "\\x{3A}A-Z\\x{5F}a-z\\x{C0}-\\x{D6}\\x{D8}-\\x{F6}\\x{F8}-\\x{2FF}\\x{370}-\\x{37D}\\x{37F}-\\x{1FFF}\\x{200C}-\\x{200D}\\x{2070}-\\x{218F}\\x{2C00}-\\x{2FEF}\\x{3001}-\\x{D7FF}\\x{F900}-\\x{FDCF}\\x{FDF0}-\\x{FFFD}\\x{10000}-\\x{EFFFF}";
+/// https://www.w3.org/TR/xml11/#NT-NameChar, besides `NAME_START_CHARS`
+const NAME_CHARS: &'static str = "\\x{2D}\\x{2E}0-9\\x{B7}\\x{300}-\\x{36F}\\x{203F}-\\x{2040}";
+
+/// https://www.w3.org/TR/xmlschema11-2/#nt-IsCategory
+const CATEGORIES: &'static [&'static str] = &[
+    "L", "Lu", "Ll", "Lt", "Lm", "Lo",
+    "M", "Mn", "Mc", "Me",
+    "N", "Nd", "Nl", "No",
+    "P", "Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po",
+    "Z", "Zs", "Zl", "Zp",
+    "S", "Sm", "Sc", "Sk", "So",
+    "C", "Cc", "Cf", "Co", "Cn",
+];
+
+/// What an escape stands for.
+enum Escape {
+    /// A single character, which may be a bound of a range.
+    Char(char),
+    /// A set of characters, in the syntax of the `regex` crate.
+    Set(String),
+}
+
+fn literal(c: char) -> String {
+    format!("\\x{{{:X}}}", c as u32)
+}
+
+struct Translator<'a> {
+    chars: Peekable<Chars<'a>>,
+    out: String,
+}
+
+impl<'a> Translator<'a> {
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-regExp
+    fn reg_exp(&mut self) -> Result<(), String> {
+        loop {
+            self.branch()?;
+            match self.chars.peek() {
+                Some('|') => {
+                    self.chars.next();
+                    self.out.push('|');
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-branch
+    fn branch(&mut self) -> Result<(), String> {
+        loop {
+            match self.chars.peek() {
+                None | Some('|') | Some(')') => return Ok(()),
+                _ => self.piece()?,
+            }
+        }
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-piece
+    fn piece(&mut self) -> Result<(), String> {
+        match self.chars.next() {
+            Some('(') => {
+                self.out.push_str("(?:");
+                self.reg_exp()?;
+                if self.chars.next() != Some(')') {
+                    return Err("unclosed group".to_string());
+                }
+                self.out.push(')');
+            },
+            Some('[') => {
+                let class = self.char_class_expr()?;
+                self.out.push_str(&class);
+            },
+            Some('\\') => {
+                match self.escape()? {
+                    Escape::Char(c) => self.out.push_str(&literal(c)),
+                    Escape::Set(set) => self.out.push_str(&set),
+                }
+            },
+            Some('.') => self.out.push_str("[^\\n\\r]"),
+            Some(c @ '?') | Some(c @ '*') | Some(c @ '+') | Some(c @ '{') |
+            Some(c @ '}') | Some(c @ ']') => return Err(format!("unexpected {:?}", c)),
+            Some(c) => self.out.push_str(&literal(c)),
+            None => unreachable!(),
+        }
+        self.quantifier()
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-quantifier
+    fn quantifier(&mut self) -> Result<(), String> {
+        match self.chars.peek() {
+            Some(&c) if c == '?' || c == '*' || c == '+' => {
+                self.chars.next();
+                self.out.push(c);
+            },
+            Some('{') => {
+                self.chars.next();
+                let min = self.quantity()?.ok_or("missing minimum in quantifier")?;
+                let max = match self.chars.next() {
+                    Some('}') => Some(min),
+                    Some(',') => {
+                        let max = self.quantity()?;
+                        if self.chars.next() != Some('}') {
+                            return Err("unclosed quantifier".to_string());
+                        }
+                        max
+                    },
+                    _ => return Err("unclosed quantifier".to_string()),
+                };
+                match max {
+                    Some(max) if max < min => return Err(format!("quantifier {{{},{}}} has its maximum below its minimum", min, max)),
+                    Some(max) if max == min => self.out.push_str(&format!("{{{}}}", min)),
+                    Some(max) => self.out.push_str(&format!("{{{},{}}}", min, max)),
+                    None => self.out.push_str(&format!("{{{},}}", min)),
+                }
+            },
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-QuantExact
+    fn quantity(&mut self) -> Result<Option<u32>, String> {
+        let mut digits = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            self.chars.next();
+        }
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        digits.parse().map(Some).map_err(|_| format!("quantity {} is too large", digits))
+    }
+
+    /// Parses what follows a `\`.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-2/#cces
+    fn escape(&mut self) -> Result<Escape, String> {
+        let set = |s: &str| Ok(Escape::Set(s.to_string()));
+        match self.chars.next() {
+            Some('n') => Ok(Escape::Char('\n')),
+            Some('r') => Ok(Escape::Char('\r')),
+            Some('t') => Ok(Escape::Char('\t')),
+            Some(c @ '\\') | Some(c @ '|') | Some(c @ '.') | Some(c @ '?') |
+            Some(c @ '*') | Some(c @ '+') | Some(c @ '(') | Some(c @ ')') |
+            Some(c @ '{') | Some(c @ '}') | Some(c @ '-') | Some(c @ '[') |
+            Some(c @ ']') | Some(c @ '^') => Ok(Escape::Char(c)),
+            Some('s') => set("[\\x{20}\\t\\n\\r]"),
+            Some('S') => set("[^\\x{20}\\t\\n\\r]"),
+            Some('i') => Ok(Escape::Set(format!("[{}]", NAME_START_CHARS))),
+            Some('I') => Ok(Escape::Set(format!("[^{}]", NAME_START_CHARS))),
+            Some('c') => Ok(Escape::Set(format!("[{}{}]", NAME_START_CHARS, NAME_CHARS))),
+            Some('C') => Ok(Escape::Set(format!("[^{}{}]", NAME_START_CHARS, NAME_CHARS))),
+            Some('d') => set("\\p{Nd}"),
+            Some('D') => set("\\P{Nd}"),
+            Some('w') => set("[^\\p{P}\\p{Z}\\p{C}]"),
+            Some('W') => set("[\\p{P}\\p{Z}\\p{C}]"),
+            Some(c @ 'p') | Some(c @ 'P') => {
+                if self.chars.next() != Some('{') {
+                    return Err(format!("missing {{ after \\{}", c));
+                }
+                let mut name = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed \\{}{{", c)),
+                    }
+                }
+                if name.starts_with("Is") {
+                    Err(format!("block escape \\{}{{{}}} is not supported", c, name))
+                }
+                else if CATEGORIES.contains(&&name[..]) {
+                    Ok(Escape::Set(format!("\\{}{{{}}}", c, name)))
+                }
+                else {
+                    Err(format!("unknown category {:?}", name))
+                }
+            },
+            Some(c) => Err(format!("unknown escape \\{}", c)),
+            None => Err("pattern ends with \\".to_string()),
+        }
+    }
+
+    /// Parses a character class, whose `[` was just read.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-2/#nt-charClassExpr
+    fn char_class_expr(&mut self) -> Result<String, String> {
+        let negative = self.chars.peek() == Some(&'^');
+        if negative {
+            self.chars.next();
+        }
+        let mut items = String::new();
+        let mut subtraction = None;
+        let mut first = true;
+        loop {
+            let start = match self.chars.next() {
+                Some(']') if first => return Err("empty character class".to_string()),
+                Some(']') => break,
+                Some('-') if !first && self.chars.peek() == Some(&'[') => {
+                    self.chars.next();
+                    subtraction = Some(self.char_class_expr()?);
+                    if self.chars.next() != Some(']') {
+                        return Err("a class subtraction must end its class".to_string());
+                    }
+                    break;
+                },
+                // A hyphen is only a character of its own at either end
+                // of a group.
+                Some('-') if first || self.chars.peek() == Some(&']') => Escape::Char('-'),
+                Some('-') => return Err("unescaped - in a character class".to_string()),
+                Some('[') => return Err("unescaped [ in a character class".to_string()),
+                Some('\\') => self.escape()?,
+                Some(c) => Escape::Char(c),
+                None => return Err("unclosed character class".to_string()),
+            };
+            first = false;
+            match start {
+                Escape::Char(start) => {
+                    let mut lookahead = self.chars.clone();
+                    let is_range = lookahead.next() == Some('-') && lookahead.peek().map_or(false, |&c| c != '[' && c != ']');
+                    if is_range {
+                        self.chars.next();
+                        let end = match self.chars.next() {
+                            Some('\\') => match self.escape()? {
+                                Escape::Char(c) => c,
+                                Escape::Set(_) => return Err("a range cannot end with a set of characters".to_string()),
+                            },
+                            Some(c) => c,
+                            None => unreachable!(),
+                        };
+                        if end < start {
+                            return Err(format!("range {:?}-{:?} is out of order", start, end));
+                        }
+                        items.push_str(&format!("{}-{}", literal(start), literal(end)));
+                    }
+                    else {
+                        items.push_str(&literal(start));
+                    }
+                },
+                Escape::Set(set) => items.push_str(&set),
+            }
+        }
+        let class = format!("[{}{}]", if negative { "^" } else { "" }, items);
+        match subtraction {
+            Some(subtraction) => Ok(format!("[{}--{}]", class, subtraction)),
+            None => Ok(class),
+        }
+    }
+}
+
+/// Translates a pattern to the syntax of the `regex` crate, anchored so it
+/// has to match whole values.
+pub fn translate(pattern: &str) -> Result<String, String> {
+    let mut translator = Translator { chars: pattern.chars().peekable(), out: String::new() };
+    translator.out.push_str("\\A(?:");
+    translator.reg_exp()?;
+    if translator.chars.next().is_some() {
+        // reg_exp only stops early at a closing parenthesis.
+        return Err("unmatched )".to_string());
+    }
+    translator.out.push_str(")\\z");
+    Ok(translator.out)
+}
+
+/// Translates and compiles a pattern; the processor uses it to reject
+/// invalid patterns.
+pub fn compile(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&translate(pattern)?).map_err(|e| e.to_string())
+}
+
+thread_local! {
+    /// Patterns compiled so far, or None for invalid ones.
+    static COMPILED: RefCell<HashMap<String, Option<Regex>>> = RefCell::new(HashMap::new());
+}
+
+/// Whether a whole value matches a pattern. Values never match invalid
+/// patterns.
+pub fn is_match(pattern: &str, value: &str) -> bool {
+    COMPILED.with(|compiled| {
+        let mut compiled = compiled.borrow_mut();
+        if !compiled.contains_key(pattern) {
+            compiled.insert(pattern.to_string(), compile(pattern).ok());
+        }
+        match compiled[pattern] {
+            Some(ref regex) => regex.is_match(value),
+            None => false,
+        }
+    })
+}
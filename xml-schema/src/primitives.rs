@@ -1,14 +1,17 @@
+use std::borrow::Cow;
 use std::cmp::max;
 use std::str::FromStr;
 use std::marker::PhantomData;
 use std::fmt;
+use std::rc::Rc;
 
 use bigdecimal::BigDecimal;
-use num_traits::{Zero, One};
+use num_traits::{Zero, One, ToPrimitive};
 
 use xmlparser::{Token as XmlToken, ElementEnd, StrSpan};
 
-use support::{ParseXml, ParseXmlStr, Stream, ParseContext, ParentContext, Facets, BigFloatNotNaN};
+use support::{ParseXml, ParseXmlStr, Stream, ParseContext, ParentContext, Facets, BigFloatNotNaN, NotationDeclaration, list_items, list_facets_allow, store_decoded};
+use uri::UriReference;
 use xml_utils::*;
 
 macro_rules! return_split {
@@ -27,57 +30,136 @@ macro_rules! validate_str {
         let s: &&str = &$s;
         if let Some(ref enumeration) = facets.enumeration {
             if !enumeration.contains(s) {
-                panic!("Expected one of {:?}, got {:?}", enumeration, s);
+                return None;
             }
         }
         if let Some(ref length) = facets.length {
             if s.len() != *length {
-                panic!("{:?} has length != {}", s, length);
+                return None;
             }
         }
         if let Some(ref min_length) = facets.min_length {
             if s.len() < *min_length {
-                panic!("{:?} has length < {}", s, min_length);
+                return None;
             }
         }
         if let Some(ref max_length) = facets.max_length {
             if s.len() > *max_length {
-                panic!("{:?} has length > {}", s, max_length);
+                return None;
             }
         }
+        if !facets.matches_patterns(s) {
+            return None;
+        }
     }}
 }
 
-macro_rules! validate_int {
-    ( $n:expr, $facets:expr) => {{
-        let n: BigDecimal = $n.into();
-        validate_decimal!(n, $facets);
-    }}
-}
 macro_rules! validate_decimal {
-    ( $n:expr, $facets:expr) => {{
+    ( $n:expr, $lexical:expr, $facets:expr) => {{
         let facets = $facets;
         let n: BigFloatNotNaN = $n.into();
+        // Enumerations are compared by value.
+        if let Some(ref enumeration) = facets.enumeration {
+            if !enumeration.iter().any(|e| BigFloatNotNaN::from_str(e).ok().map_or(false, |e| e == n)) {
+                return None;
+            }
+        }
         if let Some(ref min_exclusive) = facets.min_exclusive {
             if n <= *min_exclusive {
-                panic!("{} is <= {}", n, min_exclusive);
+                return None;
             }
         }
         if let Some(ref min_inclusive) = facets.min_inclusive {
             if n < *min_inclusive {
-                panic!("{} is < {}", n, min_inclusive);
+                return None;
             }
         }
         if let Some(ref max_exclusive) = facets.max_exclusive {
             if n >= *max_exclusive {
-                panic!("{} is >= {}", n, max_exclusive);
+                return None;
             }
         }
         if let Some(ref max_inclusive) = facets.max_inclusive {
             if n > *max_inclusive {
-                panic!("{} is > {}", n, max_inclusive);
+                return None;
+            }
+        }
+        if !facets.matches_patterns($lexical) {
+            return None;
+        }
+    }}
+}
+
+macro_rules! validate_float {
+    ( $n:expr, $type:ty, $lexical:expr, $facets:expr) => {{
+        let facets = $facets;
+        let n: $type = $n;
+        // Facet values are rounded to the value space first, and NaN
+        // is incomparable, so it fails every bound. So does a bound which
+        // cannot be rounded.
+        if let Some(ref min_exclusive) = facets.min_exclusive {
+            if !(n > min_exclusive.round::<$type>()?) {
+                return None;
+            }
+        }
+        if let Some(ref min_inclusive) = facets.min_inclusive {
+            if !(n >= min_inclusive.round::<$type>()?) {
+                return None;
+            }
+        }
+        if let Some(ref max_exclusive) = facets.max_exclusive {
+            if !(n < max_exclusive.round::<$type>()?) {
+                return None;
+            }
+        }
+        if let Some(ref max_inclusive) = facets.max_inclusive {
+            if !(n <= max_inclusive.round::<$type>()?) {
+                return None;
+            }
+        }
+        if let Some(ref enumeration) = facets.enumeration {
+            let matches = |e: &&str| match parse_float_rep::<$type>(e) {
+                Some(e) => e == n || (e.is_nan() && n.is_nan()),
+                None => false,
+            };
+            if !enumeration.iter().any(matches) {
+                return None;
             }
         }
+        if !facets.matches_patterns($lexical) {
+            return None;
+        }
+    }}
+}
+
+macro_rules! validate_binary {
+    ( $bytes:expr, $decode:expr, $lexical:expr, $facets:expr) => {{
+        let facets = $facets;
+        let bytes: &[u8] = &$bytes;
+        // Enumerations are compared by value, lengths are in octets.
+        if let Some(ref enumeration) = facets.enumeration {
+            if !enumeration.iter().any(|e| $decode(e).map_or(false, |e| &e[..] == bytes)) {
+                return None;
+            }
+        }
+        if let Some(ref length) = facets.length {
+            if bytes.len() != *length {
+                return None;
+            }
+        }
+        if let Some(ref min_length) = facets.min_length {
+            if bytes.len() < *min_length {
+                return None;
+            }
+        }
+        if let Some(ref max_length) = facets.max_length {
+            if bytes.len() > *max_length {
+                return None;
+            }
+        }
+        if !facets.matches_patterns($lexical) {
+            return None;
+        }
     }}
 }
 
@@ -95,6 +177,30 @@ pub const PRIMITIVE_TYPES: &[(&'static str, &'static str)] = &[
     ("date", "Date"),
     ("duration", "Duration"),
     ("decimal", "Decimal"),
+    ("integer", "Integer"),
+    ("long", "Long"),
+    ("int", "Int"),
+    ("short", "Short"),
+    ("byte", "Byte"),
+    ("unsignedLong", "UnsignedLong"),
+    ("unsignedInt", "UnsignedInt"),
+    ("unsignedShort", "UnsignedShort"),
+    ("unsignedByte", "UnsignedByte"),
+    ("float", "Float"),
+    ("double", "Double"),
+    ("hexBinary", "HexBinary"),
+    ("base64Binary", "Base64Binary"),
+    ("normalizedString", "NormalizedString"),
+    ("Name", "Name"),
+    ("NMTOKEN", "Nmtoken"),
+    ("NMTOKENS", "Nmtokens"),
+    ("language", "Language"),
+    ("ENTITY", "Entity"),
+    ("ENTITIES", "Entities"),
+    ("NOTATION", "Notation"),
+    ("ID", "Id"),
+    ("IDREF", "IdRef"),
+    ("IDREFS", "IdRefs"),
     ];
 
 pub type DateTime<'input> = Token<'input>; // TODO
@@ -145,37 +251,58 @@ pub struct QName<'input> {
 }
 impl<'input> ParseXmlStr<'input> for QName<'input> {
     const NODE_NAME: &'static str = "QName";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, QName<'input>)> {
-        if input.len() == 0 {
-            return None;
-        }
-        let f = &mut |prefix, local| QName {
-            namespace: parent_context.namespaces.get(prefix).cloned(),
-            local_name: local
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, _facets: &Facets<'a>) -> Option<(&'input str, QName<'input>)> {
+        let end = input.find(is_whitespace).unwrap_or(input.len());
+        let qname = &input[0..end];
+        let (prefix, local_name) = match qname.find(':') {
+            Some(i) if i > 0 => (&qname[0..i], &qname[i+1..]),
+            Some(_) => return None,
+            None => ("", qname),
         };
-        let mut i1 = 0;
-        for (i, c) in input.char_indices() {
-            if c == ':' {
-                i1 = i;
-            }
-            else if c == ' ' { // TODO
-                if i == 0 || i <= i1+1 {
-                    return None;
-                }
-                if i1 > 0 {
-                    return Some((&input[i..], f(&input[0..i1+1], &input[i1+1..i+1])))
-                }
-                else {
-                    return Some((&input[i..], f("", &input[0..i+1])))
-                }
-            }
-        }
-        if i1 > 0 {
-            return Some(("", f(&input[0..i1], &input[i1+1..])))
+        if local_name.is_empty() || local_name.contains(':') {
+            return None;
         }
-        else {
-            return Some(("", f("", input)))
+        // Unprefixed names are in the default namespace, if any, and
+        // prefixes must be bound.
+        //
+        // https://www.w3.org/TR/xmlschema11-2/#QName
+        let namespace = parent_context.resolve_prefix(prefix).ok()?;
+        Some((&input[end..], QName { namespace, local_name }))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#NOTATION
+///
+/// The QName of a notation declared by the schemas, as given by
+/// `ParseContext::notations`.
+#[derive(Debug, PartialEq)]
+pub struct Notation<'input> {
+    pub name: QName<'input>,
+    pub declaration: &'static NotationDeclaration,
+}
+
+impl<'input> ParseXmlStr<'input> for Notation<'input> {
+    const NODE_NAME: &'static str = "NOTATION";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Notation<'input>)> {
+        // Unprefixed names are in the default namespace, like element names.
+        let (output, name) = QName::parse_self_xml_str(input, parse_context, parent_context, &Facets::default())?;
+        // NOTATION can only be used through an enumeration of notation
+        // names. They are compared by local name, as their prefixes are
+        // bound in the schema; the namespace is checked against the
+        // declarations below.
+        //
+        // https://www.w3.org/TR/xmlschema11-2/#enumeration-required-notation
+        match facets.enumeration {
+            Some(ref enumeration) if enumeration.iter().any(|e| e.rsplit(':').next() == Some(name.local_name)) => (),
+            _ => return None,
         }
+        let mut lexical_facets = facets.clone();
+        lexical_facets.enumeration = None;
+        validate_str!(&input[0..input.len()-output.len()], &lexical_facets);
+        let declaration = parse_context.notations().iter().find(|declaration| {
+            declaration.namespace == name.namespace.unwrap_or("") && declaration.name == name.local_name
+        })?;
+        Some((output, Notation { name, declaration }))
     }
 }
 
@@ -210,23 +337,47 @@ impl<'input> fmt::Display for QName<'input> {
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#anyURI
+///
+/// The second field is the base URI in scope where the value appeared
+/// (see `ParentContext::base`), which relative references are resolved
+/// against.
 #[derive(Debug, PartialEq, Default)]
-pub struct AnyUri<'input>(pub &'input str);
+pub struct AnyUri<'input>(pub &'input str, pub Option<Rc<String>>);
 impl<'input> ParseXmlStr<'input> for AnyUri<'input> {
     const NODE_NAME: &'static str = "AnyUri";
-    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, AnyUri<'input>)> {
-        if input.len() == 0 {
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, AnyUri<'input>)> {
+        // The whole value is one reference, whitespace-collapsed; spaces
+        // are allowed and stand for %20.
+        //
+        // https://www.w3.org/TR/xmlschema11-2/#anyURI
+        let collapsed = list_items(input).join(" ");
+        let value: &'input str = match input.find(&collapsed[..]) {
+            Some(start) => &input[start..start+collapsed.len()],
+            None => store_decoded(collapsed),
+        };
+        if value.is_empty() {
             return None;
         }
-        for (i, c) in input.char_indices() {
-            if c == ' ' { // TODO
-                if i == 0 {
-                    return None;
-                }
-                return Some((&input[i..], AnyUri(&input[0..i])))
-            }
+        UriReference::parse(value)?;
+        validate_str!(value, facets);
+        Some(("", AnyUri(value, parent_context.base.clone())))
+    }
+}
+impl<'input> AnyUri<'input> {
+    /// Returns the components of the reference, or None if it is not a
+    /// valid reference.
+    pub fn components(&self) -> Option<UriReference<'input>> {
+        UriReference::parse(self.0)
+    }
+
+    /// Returns this reference resolved against the base URI in scope,
+    /// or unchanged if there is none.
+    pub fn resolve(&self) -> Option<String> {
+        match self.1 {
+            Some(ref base) => Some(self.components()?.resolve(&UriReference::parse(base)?)),
+            None => Some(self.0.to_string()),
         }
-        Some(("", AnyUri(input)))
     }
 }
 
@@ -242,49 +393,90 @@ impl<'input> ParseXml<'input> for AnyURIElement<'input> {
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#integer
+///
+/// Arbitrary-precision; the bounded types derived from it (`Long`,
+/// `Int`, ...) use native integers instead.
 #[derive(Debug, PartialEq, Default)]
-pub struct Integer<'input>(pub i64, PhantomData<&'input ()>);
+pub struct Integer<'input>(pub BigDecimal, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for Integer<'input> {
     const NODE_NAME: &'static str = "Integer";
     fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Integer<'input>)> {
-        let mut iter = input.char_indices();
-        let mut n: i64 = 0;
-        let mut multiplier = 1;
-        let c = iter.next()?.1;
-        match c {
-            '+' => multiplier = 1,
-            '-' => multiplier = -1,
-            '0'..='9' => n = (c as i64) - ('0' as i64),
-            _ => return None,
+        let bytes = input.as_bytes();
+        let mut i = 0;
+        let negative = bytes.get(0) == Some(&b'-');
+        if negative || bytes.get(0) == Some(&b'+') {
+            i += 1;
         }
-
-        if c == '+' || c == '-' {
-            let c = iter.next()?.1;
-            match c {
-                '0'..='9' => n = (c as i64) - ('0' as i64),
-                _ => return None,
-            }
+        let digits_start = i;
+        while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+            i += 1;
+        }
+        if i == digits_start {
+            return None;
         }
+        let digits = if negative { &input[0..i] } else { &input[digits_start..i] };
+        let res = BigDecimal::from_str(digits).ok()?;
+        validate_decimal!(res.clone(), &input[0..i], facets);
+        Some((&input[i..], Integer(res, PhantomData::default())))
+    }
+}
 
-        for (i,c) in iter {
-            match c {
-                '0'..='9' => n = n * 10 + ((c as i64) - ('0' as i64)),
-                _ => {
-                    let res = multiplier * n;
-                    validate_int!(res, facets);
-                    return Some((&input[i..], Integer(res, PhantomData::default())));
+macro_rules! impl_native_integer {
+    ( $name:ident, $type:ty, $doc:expr ) => {
+        #[doc = $doc]
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+        pub struct $name<'input>(pub $type, PhantomData<&'input ()>);
+        impl<'input> ParseXmlStr<'input> for $name<'input> {
+            const NODE_NAME: &'static str = stringify!($name);
+            fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, $name<'input>)> {
+                let bytes = input.as_bytes();
+                let mut i = 0;
+                let negative = bytes.get(0) == Some(&b'-');
+                if negative || bytes.get(0) == Some(&b'+') {
+                    i += 1;
+                }
+                let digits_start = i;
+                while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                    i += 1;
+                }
+                if i == digits_start {
+                    return None;
+                }
+                // Values out of the range of the native type are rejected
+                // by from_str, except "-0" for the unsigned types.
+                let n: $type = match <$type>::from_str(&input[0..i]) {
+                    Ok(n) => n,
+                    Err(_) if bytes[digits_start..i].iter().all(|&b| b == b'0') => 0,
+                    Err(_) => return None,
+                };
+                if facets.min_exclusive.is_some() || facets.min_inclusive.is_some() || facets.max_exclusive.is_some() || facets.max_inclusive.is_some() || facets.enumeration.is_some() {
+                    let digits = if negative { &input[0..i] } else { &input[digits_start..i] };
+                    validate_decimal!(BigDecimal::from_str(digits).ok()?, &input[0..i], facets);
                 }
+                else if !facets.matches_patterns(&input[0..i]) {
+                    return None;
+                }
+                Some((&input[i..], $name(n, PhantomData::default())))
             }
         }
-        
-        let res = multiplier * n;
-        validate_int!(res, facets);
-        Some(("", Integer(res, PhantomData::default())))
     }
 }
 
+impl_native_integer!(Long, i64, "https://www.w3.org/TR/xmlschema11-2/#long");
+impl_native_integer!(Int, i32, "https://www.w3.org/TR/xmlschema11-2/#int");
+impl_native_integer!(Short, i16, "https://www.w3.org/TR/xmlschema11-2/#short");
+impl_native_integer!(Byte, i8, "https://www.w3.org/TR/xmlschema11-2/#byte");
+impl_native_integer!(UnsignedLong, u64, "https://www.w3.org/TR/xmlschema11-2/#unsignedLong");
+impl_native_integer!(UnsignedInt, u32, "https://www.w3.org/TR/xmlschema11-2/#unsignedInt");
+impl_native_integer!(UnsignedShort, u16, "https://www.w3.org/TR/xmlschema11-2/#unsignedShort");
+impl_native_integer!(UnsignedByte, u8, "https://www.w3.org/TR/xmlschema11-2/#unsignedByte");
+
+/// https://www.w3.org/TR/xmlschema11-2/#nonNegativeInteger
+///
+/// Arbitrary-precision, like `Integer`.
 #[derive(Debug, PartialEq)]
-pub struct NonNegativeInteger<'input>(pub u64, PhantomData<&'input ()>);
+pub struct NonNegativeInteger<'input>(pub BigDecimal, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for NonNegativeInteger<'input> {
     const NODE_NAME: &'static str = "NonNegativeInteger";
     fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Option<(&'input str, NonNegativeInteger<'input>)> {
@@ -292,12 +484,21 @@ impl<'input> ParseXmlStr<'input> for NonNegativeInteger<'input> {
         let mut facets = facets.clone();
         facets.min_inclusive = Some(min);
         let (output, n) = Integer::parse_self_xml_str(input, parse_context, parent_context, &facets)?;
-        Some((output, NonNegativeInteger(n.0 as u64, PhantomData::default())))
+        Some((output, NonNegativeInteger(n.0, PhantomData::default())))
+    }
+}
+impl<'input> NonNegativeInteger<'input> {
+    /// Returns the value, or `usize::MAX` if it does not fit.
+    pub fn to_usize_saturating(&self) -> usize {
+        self.0.to_usize().unwrap_or(usize::max_value())
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#positiveInteger
+///
+/// Arbitrary-precision, like `Integer`.
 #[derive(Debug, PartialEq)]
-pub struct PositiveInteger<'input>(pub u64, PhantomData<&'input ()>);
+pub struct PositiveInteger<'input>(pub BigDecimal, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for PositiveInteger<'input> {
     const NODE_NAME: &'static str = "PositiveInteger";
     fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets) -> Option<(&'input str, PositiveInteger<'input>)> {
@@ -308,6 +509,12 @@ impl<'input> ParseXmlStr<'input> for PositiveInteger<'input> {
         Some((output, PositiveInteger(n.0, PhantomData::default())))
     }
 }
+impl<'input> PositiveInteger<'input> {
+    /// Returns the value, or `usize::MAX` if it does not fit.
+    pub fn to_usize_saturating(&self) -> usize {
+        self.0.to_usize().unwrap_or(usize::max_value())
+    }
+}
 
 #[derive(Debug, PartialEq, Default)]
 pub struct Decimal<'input>(pub BigDecimal, PhantomData<&'input ()>);
@@ -320,7 +527,7 @@ impl<'input> ParseXmlStr<'input> for Decimal<'input> {
                     Ok(res) => res,
                     Err(_) => return None,
                 };
-                validate_decimal!(res.clone(), facets);
+                validate_decimal!(res.clone(), &input[0..i], facets);
                 return Some((&input[i..], Decimal(res, PhantomData::default())))
             }
         }
@@ -328,11 +535,223 @@ impl<'input> ParseXmlStr<'input> for Decimal<'input> {
             Ok(res) => res,
             Err(_) => return None,
         };
-        validate_decimal!(res.clone(), facets);
+        validate_decimal!(res.clone(), input, facets);
         Some(("", Decimal(res, PhantomData::default())))
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#nt-floatRep
+///
+/// Checks the lexical form, then rounds it to the nearest `f32` or `f64`.
+fn parse_float_rep<F: FromStr>(s: &str) -> Option<F> {
+    let rounded = match s {
+        "INF" | "+INF" => "inf".parse(),
+        "-INF" => "-inf".parse(),
+        "NaN" => "NaN".parse(),
+        _ => {
+            let bytes = s.as_bytes();
+            let skip_digits = |mut i: usize| {
+                while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                    i += 1;
+                }
+                i
+            };
+            let mut i = 0;
+            if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+                i += 1;
+            }
+            let mantissa_start = i;
+            i = skip_digits(i);
+            let mut nb_digits = i - mantissa_start;
+            if i < bytes.len() && bytes[i] == b'.' {
+                let fraction_start = i + 1;
+                i = skip_digits(fraction_start);
+                nb_digits += i - fraction_start;
+            }
+            if nb_digits == 0 {
+                return None;
+            }
+            if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+                i += 1;
+                if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+                    i += 1;
+                }
+                let exponent_start = i;
+                i = skip_digits(exponent_start);
+                if i == exponent_start {
+                    return None;
+                }
+            }
+            if i != bytes.len() {
+                return None;
+            }
+            s.parse()
+        }
+    };
+    rounded.ok()
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#f-floatCanmap
+fn fmt_float_canonical<F: ::num_traits::Float + fmt::UpperExp>(n: F, f: &mut fmt::Formatter) -> fmt::Result {
+    if n.is_nan() {
+        write!(f, "NaN")
+    }
+    else if n.is_infinite() {
+        write!(f, "{}", if n.is_sign_positive() { "INF" } else { "-INF" })
+    }
+    else {
+        // Shortest representation that rounds back to n, eg. "1.5E-1"
+        // or "1E2"; the canonical form always has a fractional part.
+        let repr = format!("{:E}", n);
+        let (mantissa, exponent) = repr.split_at(repr.find('E').unwrap());
+        if mantissa.contains('.') {
+            write!(f, "{}{}", mantissa, exponent)
+        }
+        else {
+            write!(f, "{}.0{}", mantissa, exponent)
+        }
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#float
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct Float<'input>(pub f32, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for Float<'input> {
+    const NODE_NAME: &'static str = "Float";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Float<'input>)> {
+        let end = input.find(is_whitespace).unwrap_or(input.len());
+        let n = parse_float_rep(&input[0..end])?;
+        validate_float!(n, f32, &input[0..end], facets);
+        Some((&input[end..], Float(n, PhantomData::default())))
+    }
+}
+impl<'input> fmt::Display for Float<'input> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_float_canonical(self.0, f)
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#double
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct Double<'input>(pub f64, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for Double<'input> {
+    const NODE_NAME: &'static str = "Double";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Double<'input>)> {
+        let end = input.find(is_whitespace).unwrap_or(input.len());
+        let n = parse_float_rep(&input[0..end])?;
+        validate_float!(n, f64, &input[0..end], facets);
+        Some((&input[end..], Double(n, PhantomData::default())))
+    }
+}
+impl<'input> fmt::Display for Double<'input> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_float_canonical(self.0, f)
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#nt-hexBinary
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    s.as_bytes().chunks(2).map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#hexBinary
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct HexBinary<'input>(pub Vec<u8>, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for HexBinary<'input> {
+    const NODE_NAME: &'static str = "HexBinary";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, HexBinary<'input>)> {
+        let end = input.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(input.len());
+        let bytes = decode_hex(&input[0..end])?;
+        validate_binary!(bytes, decode_hex, &input[0..end], facets);
+        Some((&input[end..], HexBinary(bytes, PhantomData::default())))
+    }
+}
+impl<'input> fmt::Display for HexBinary<'input> {
+    /// https://www.w3.org/TR/xmlschema11-2/#f-hexBinaryCanonical
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// https://www.w3.org/TR/xmlschema11-2/#nt-Base64Binary
+///
+/// Whitespace may appear anywhere, and the bits padding the last
+/// octet must be zero.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let mut digits = Vec::with_capacity(s.len());
+    let mut padding = 0;
+    for c in s.chars() {
+        match c {
+            _ if is_whitespace(c) => (),
+            '=' => padding += 1,
+            _ if padding > 0 => return None,
+            'A'..='Z' => digits.push(c as u32 - 'A' as u32),
+            'a'..='z' => digits.push(c as u32 - 'a' as u32 + 26),
+            '0'..='9' => digits.push(c as u32 - '0' as u32 + 52),
+            '+' => digits.push(62),
+            '/' => digits.push(63),
+            _ => return None,
+        }
+    }
+    if padding > 2 || (digits.len() + padding) % 4 != 0 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let nb_bytes = chunk.len() - 1;
+        let n = chunk.iter().fold(0u32, |acc, &d| acc << 6 | d) << (6 * (4 - chunk.len()));
+        if n & ((1 << (24 - 8 * nb_bytes)) - 1) != 0 {
+            return None;
+        }
+        for i in 0..nb_bytes {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#base64Binary
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Base64Binary<'input>(pub Vec<u8>, PhantomData<&'input ()>);
+impl<'input> ParseXmlStr<'input> for Base64Binary<'input> {
+    const NODE_NAME: &'static str = "Base64Binary";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Base64Binary<'input>)> {
+        let is_base64_char = |c: char| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '=' || is_whitespace(c);
+        let end = input.find(|c: char| !is_base64_char(c)).unwrap_or(input.len());
+        // Trailing whitespace is not part of the value.
+        let end = input[0..end].rfind(|c: char| !is_whitespace(c)).map_or(0, |i| i + 1);
+        let bytes = decode_base64(&input[0..end])?;
+        validate_binary!(bytes, decode_base64, &input[0..end], facets);
+        Some((&input[end..], Base64Binary(bytes, PhantomData::default())))
+    }
+}
+impl<'input> fmt::Display for Base64Binary<'input> {
+    /// https://www.w3.org/TR/xmlschema11-2/#f-base64Canonical
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for chunk in self.0.chunks(3) {
+            let n = chunk.iter().fold(0u32, |acc, &b| acc << 8 | b as u32) << (8 * (3 - chunk.len()));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    write!(f, "{}", BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char)?;
+                }
+                else {
+                    write!(f, "=")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Default)]
 pub struct Any<'input>(pub Vec<XmlToken<'input>>);
 impl<'input> ParseXml<'input> for Any<'input> {
@@ -346,7 +765,7 @@ impl<'input> ParseXml<'input> for Any<'input> {
             match tok {
                 XmlToken::Whitespaces(_) => (),
                 XmlToken::Comment(_) => (),
-                XmlToken::Text(_) => (),
+                XmlToken::Text(_) | XmlToken::Cdata(_) if stream.allows_text() => (),
                 XmlToken::ElementStart(prefix, name) => {
                     tag_stack.push(QName::from_strspans(prefix, name));
                     tokens.push(tok);
@@ -395,6 +814,7 @@ impl<'input> ParseXmlStr<'input> for XmlString<'input> {
                 return_split!(input, i, XmlString, validate_str!, facets);
             }
         }
+        validate_str!(input, facets);
         Some(("", XmlString(input)))
     }
 }
@@ -430,22 +850,292 @@ pub struct NcName<'input>(pub &'input str);
 impl<'input> ParseXmlStr<'input> for NcName<'input> {
     const NODE_NAME: &'static str = "NcName";
     fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, NcName<'input>)> {
+        // whiteSpace="collapse"
+        let input = input.trim_matches(is_whitespace);
         let mut iter = input.char_indices();
         let c = iter.next()?.1;
         if c == ':' || !is_name_start_char(c) { return None };
 
-        for (i, c) in iter {
-            if c == ':' || !is_name_char(c) {
-                return_split!(input, i, NcName, validate_str!, facets);
-            }
+        if iter.any(|(_, c)| c == ':' || !is_name_char(c)) {
+            return None;
         }
 
+        validate_str!(input, facets);
         Some(("", NcName(input)))
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#normalizedString
+///
+/// Tabs, carriage returns and line feeds are replaced with spaces
+/// (`whiteSpace="replace"`) before the facets are checked.
+#[derive(Debug, PartialEq)]
+pub struct NormalizedString<'input>(pub Cow<'input, str>);
+
+impl<'input> ParseXmlStr<'input> for NormalizedString<'input> {
+    const NODE_NAME: &'static str = "NormalizedString";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, NormalizedString<'input>)> {
+        if input.chars().any(|c| !is_xml_char(c)) {
+            return None;
+        }
+        let string = if input.contains(|c: char| c == '\t' || c == '\r' || c == '\n') {
+            Cow::Owned(input.replace(|c: char| c == '\t' || c == '\r' || c == '\n', " "))
+        }
+        else {
+            Cow::Borrowed(input)
+        };
+        validate_str!(&string[..], facets);
+        Some(("", NormalizedString(string)))
+    }
+}
+
+impl<'input> Default for NormalizedString<'input> {
+    fn default() -> Self {
+        NormalizedString(Cow::Borrowed(""))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#Name
+#[derive(Debug, PartialEq, Default)]
+pub struct Name<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for Name<'input> {
+    const NODE_NAME: &'static str = "Name";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Name<'input>)> {
+        // whiteSpace="collapse"
+        let input = input.trim_matches(is_whitespace);
+        let mut iter = input.char_indices();
+        let c = iter.next()?.1;
+        if !is_name_start_char(c) { return None };
+
+        if iter.any(|(_, c)| !is_name_char(c)) {
+            return None;
+        }
+
+        validate_str!(input, facets);
+        Some(("", Name(input)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#NMTOKEN
+#[derive(Debug, PartialEq, Default)]
+pub struct Nmtoken<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for Nmtoken<'input> {
+    const NODE_NAME: &'static str = "NMTOKEN";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Nmtoken<'input>)> {
+        // whiteSpace="collapse"
+        let input = input.trim_matches(is_whitespace);
+        if input.len() == 0 || !input.chars().all(is_name_char) {
+            return None;
+        }
+        validate_str!(input, facets);
+        Some(("", Nmtoken(input)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#NMTOKENS
+#[derive(Debug, PartialEq, Default)]
+pub struct Nmtokens<'input>(pub Vec<Nmtoken<'input>>);
+
+impl<'input> ParseXmlStr<'input> for Nmtokens<'input> {
+    const NODE_NAME: &'static str = "NMTOKENS";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Nmtokens<'input>)> {
+        let items = list_items(input);
+        if items.is_empty() || !list_facets_allow(&items, facets) {
+            return None;
+        }
+        let mut nmtokens = Vec::new();
+        for item in items {
+            match Nmtoken::parse_self_xml_str(item, parse_context, parent_context, &Facets::default())? {
+                ("", nmtoken) => nmtokens.push(nmtoken),
+                _ => return None,
+            }
+        }
+        Some(("", Nmtokens(nmtokens)))
+    }
+}
+
+/// Irregular grandfathered tags, the only ones not matching the
+/// `langtag` production.
+///
+/// https://tools.ietf.org/html/rfc5646#section-2.2.8
+const GRANDFATHERED_LANGUAGE_TAGS: &[&str] = &[
+    "en-gb-oed", "i-ami", "i-bnn", "i-default", "i-enochian", "i-hak",
+    "i-klingon", "i-lux", "i-mingo", "i-navajo", "i-pwn", "i-tao", "i-tay",
+    "i-tsu", "sgn-be-fr", "sgn-be-nl", "sgn-ch-de",
+    ];
+
+/// Checks `tag` is a well-formed BCP 47 language tag.
+///
+/// https://tools.ietf.org/html/rfc5646#section-2.1
+fn is_language_tag(tag: &str) -> bool {
+    if GRANDFATHERED_LANGUAGE_TAGS.contains(&&tag.to_ascii_lowercase()[..]) {
+        return true;
+    }
+    let subtags: Vec<&str> = tag.split('-').collect();
+    if subtags.iter().any(|subtag| subtag.len() == 0 || subtag.len() > 8 || !subtag.chars().all(|c| c.is_ascii_alphanumeric())) {
+        return false;
+    }
+    let is_alpha = |subtag: &str| subtag.chars().all(|c| c.is_ascii_alphabetic());
+    let is_digit = |subtag: &str| subtag.chars().all(|c| c.is_ascii_digit());
+    let is_private_use = |subtag: &str| subtag.eq_ignore_ascii_case("x");
+
+    if is_private_use(subtags[0]) {
+        return subtags.len() > 1;
+    }
+    if subtags[0].len() < 2 || !is_alpha(subtags[0]) {
+        return false;
+    }
+    let mut i = 1;
+    if subtags[0].len() <= 3 {
+        // extlang
+        let extlangs_end = ::std::cmp::min(i + 3, subtags.len());
+        while i < extlangs_end && subtags[i].len() == 3 && is_alpha(subtags[i]) {
+            i += 1;
+        }
+    }
+    // script
+    if i < subtags.len() && subtags[i].len() == 4 && is_alpha(subtags[i]) {
+        i += 1;
+    }
+    // region
+    if i < subtags.len() && ((subtags[i].len() == 2 && is_alpha(subtags[i])) || (subtags[i].len() == 3 && is_digit(subtags[i]))) {
+        i += 1;
+    }
+    // variants
+    while i < subtags.len() && (subtags[i].len() >= 5 || (subtags[i].len() == 4 && subtags[i].as_bytes()[0].is_ascii_digit())) {
+        i += 1;
+    }
+    // extensions
+    while i < subtags.len() && subtags[i].len() == 1 && !is_private_use(subtags[i]) {
+        i += 1;
+        let extension_start = i;
+        while i < subtags.len() && subtags[i].len() >= 2 {
+            i += 1;
+        }
+        if i == extension_start {
+            return false;
+        }
+    }
+    // private use
+    if i < subtags.len() && is_private_use(subtags[i]) {
+        return i + 1 < subtags.len();
+    }
+    i == subtags.len()
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#language
+///
+/// Values must be well-formed BCP 47 language tags, which is stricter
+/// than the pattern given by XML Schema.
+#[derive(Debug, PartialEq, Default)]
+pub struct Language<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for Language<'input> {
+    const NODE_NAME: &'static str = "Language";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Language<'input>)> {
+        // whiteSpace="collapse"
+        let input = input.trim_matches(is_whitespace);
+        if !is_language_tag(input) {
+            return None;
+        }
+        validate_str!(input, facets);
+        Some(("", Language(input)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#ENTITY
+#[derive(Debug, PartialEq, Default)]
+pub struct Entity<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for Entity<'input> {
+    const NODE_NAME: &'static str = "ENTITY";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Entity<'input>)> {
+        let (output, NcName(name)) = NcName::parse_self_xml_str(input, parse_context, parent_context, facets)?;
+        Some((output, Entity(name)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#ENTITIES
+#[derive(Debug, PartialEq, Default)]
+pub struct Entities<'input>(pub Vec<Entity<'input>>);
+
+impl<'input> ParseXmlStr<'input> for Entities<'input> {
+    const NODE_NAME: &'static str = "ENTITIES";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Entities<'input>)> {
+        let items = list_items(input);
+        if items.is_empty() || !list_facets_allow(&items, facets) {
+            return None;
+        }
+        let mut entities = Vec::new();
+        for item in items {
+            match Entity::parse_self_xml_str(item, parse_context, parent_context, &Facets::default())? {
+                ("", entity) => entities.push(entity),
+                _ => return None,
+            }
+        }
+        Some(("", Entities(entities)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#ID
+///
+/// Values are reported to `ParseContext::on_id`, which checks they are
+/// unique.
+#[derive(Debug, PartialEq)]
+pub struct Id<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for Id<'input> {
+    const NODE_NAME: &'static str = "ID";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Id<'input>)> {
+        let (output, NcName(id)) = NcName::parse_self_xml_str(input, parse_context, parent_context, facets)?;
+        if !parent_context.check_only {
+            parse_context.on_id(id, parent_context);
+        }
+        Some((output, Id(id)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#IDREF
+#[derive(Debug, PartialEq)]
+pub struct IdRef<'input>(pub &'input str);
+
+impl<'input> ParseXmlStr<'input> for IdRef<'input> {
+    const NODE_NAME: &'static str = "IDREF";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, IdRef<'input>)> {
+        let (output, NcName(idref)) = NcName::parse_self_xml_str(input, parse_context, parent_context, facets)?;
+        if !parent_context.check_only {
+            parse_context.on_idref(idref, parent_context);
+        }
+        Some((output, IdRef(idref)))
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-2/#IDREFS
+#[derive(Debug, PartialEq)]
+pub struct IdRefs<'input>(pub Vec<IdRef<'input>>);
+
+impl<'input> ParseXmlStr<'input> for IdRefs<'input> {
+    const NODE_NAME: &'static str = "IDREFS";
+    fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, IdRefs<'input>)> {
+        let items = list_items(input);
+        if items.is_empty() || !list_facets_allow(&items, facets) {
+            return None;
+        }
+        let mut idrefs = Vec::new();
+        for item in items {
+            match IdRef::parse_self_xml_str(item, parse_context, parent_context, &Facets::default())? {
+                ("", idref) => idrefs.push(idref),
+                _ => return None,
+            }
+        }
+        Some(("", IdRefs(idrefs)))
+    }
+}
+
 #[derive(Debug, PartialEq, Default)]
-pub struct Boolean<'input>(bool, PhantomData<&'input ()>);
+pub struct Boolean<'input>(pub bool, PhantomData<&'input ()>);
 impl<'input> ParseXmlStr<'input> for Boolean<'input> {
     const NODE_NAME: &'static str = "Boolean";
     fn parse_self_xml_str<'a, TParseContext: ParseContext<'input>>(input: &'input str, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>, facets: &Facets<'a>) -> Option<(&'input str, Boolean<'input>)> {
@@ -2,30 +2,44 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::collections::{HashMap, HashSet};
 
+use num_traits::ToPrimitive;
 use xmlparser::Token as XmlToken;
 use xmlparser::{TextUnescape, XmlSpace};
 
 use parser::*;
 use names::*;
-use support::Facets;
-use primitives::{QName,NcName,AnyUri,NonNegativeInteger};
+use support;
+use support::{Facets, ProcessContents};
+use patterns;
+use primitives::{QName,NcName,AnyUri,NonNegativeInteger,Token,Boolean};
+use support::MixedItem;
 
 pub const SCHEMA_URI: &'static str = "http://www.w3.org/2001/XMLSchema";
 
 fn parse_min_occurs(x: &Option<NonNegativeInteger>) -> usize {
     match x {
         None => 1,
-        Some(n) => n.0 as usize,
+        Some(n) => n.to_usize_saturating(),
     }
 }
 fn parse_max_occurs(x: &Option<unions::UnionNonNegativeIntegerNmtoken>) -> usize {
     match x {
         None => 1,
-        Some(unions::UnionNonNegativeIntegerNmtoken::NonNegativeInteger(n)) => n.0 as usize,
+        Some(unions::UnionNonNegativeIntegerNmtoken::NonNegativeInteger(n)) => n.to_usize_saturating(),
         Some(unions::UnionNonNegativeIntegerNmtoken::Nmtoken(restrictions::Unbounded(_))) => usize::max_value(),
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-1/#dcl.ctd.ctcc.common
+/// (the `mixed` attribute of complexContent overrides complexType's)
+fn is_mixed<'input>(attr_mixed: &Option<Boolean<'input>>, complex_type_model: &xs::ComplexTypeModel<'input>) -> bool {
+    let attr_mixed = match complex_type_model {
+        xs::ComplexTypeModel::ComplexContent(ref model) => model.attr_mixed.as_ref().or(attr_mixed.as_ref()),
+        _ => attr_mixed.as_ref(),
+    };
+    attr_mixed.map(|b| b.0).unwrap_or(false)
+}
+
 fn vec_concat_opt<T: Clone>(vector: &Vec<T>, value: Option<T>) -> Vec<T>{
     let mut vector2: Vec<T> = vector.clone();
     if let Some(v) = value {
@@ -64,21 +78,247 @@ pub struct Attrs<'input> {
     pub named: Vec<(FullName<'input>, AttrUse, Option<SimpleType<'input>>)>,
     pub refs: Vec<(Option<FullName<'input>>, AttrUse, FullName<'input>)>,
     pub group_refs: Vec<FullName<'input>>,
-    pub any_attributes: bool,
+    pub any_attributes: Option<Wildcard<'input>>,
 }
 impl<'input> Attrs<'input> {
     pub fn new() -> Attrs<'input> {
-        Attrs { named: Vec::new(), refs: Vec::new(), group_refs: Vec::new(), any_attributes: false }
+        Attrs { named: Vec::new(), refs: Vec::new(), group_refs: Vec::new(), any_attributes: None }
     }
     fn extend(&mut self, other: Attrs<'input>) {
         let Attrs { named, refs, group_refs, any_attributes } = other;
         self.named.extend(named);
         self.refs.extend(refs);
         self.group_refs.extend(group_refs);
-        self.any_attributes |= any_attributes;
+        self.any_attributes = match (self.any_attributes.take(), any_attributes) {
+            (Some(w1), Some(w2)) => Some(w1.union(&w2)),
+            (w1, w2) => w1.or(w2),
+        };
+    }
+}
+
+/// Namespaces are `None` when absent.
+pub type NamespaceConstraint<'input> = support::NamespaceConstraint<Vec<Option<&'input str>>>;
+
+pub type Wildcard<'input> = support::Wildcard<Vec<Option<&'input str>>, Vec<FullName<'input>>>;
+impl<'input> Wildcard<'input> {
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-aw-union
+    pub fn union(&self, other: &Wildcard<'input>) -> Wildcard<'input> {
+        let namespaces = match (&self.namespaces, &other.namespaces) {
+            (NamespaceConstraint::Any, _) | (_, NamespaceConstraint::Any) =>
+                NamespaceConstraint::Any,
+            (NamespaceConstraint::Enumeration(e1), NamespaceConstraint::Enumeration(e2)) => {
+                let mut namespaces = e1.clone();
+                namespaces.extend(e2.iter().filter(|ns| !e1.contains(ns)).cloned());
+                NamespaceConstraint::Enumeration(namespaces)
+            },
+            (NamespaceConstraint::Not(n1), NamespaceConstraint::Not(n2)) => {
+                let namespaces: Vec<_> = n1.iter().filter(|ns| n2.contains(ns)).cloned().collect();
+                if namespaces.is_empty() {
+                    NamespaceConstraint::Any
+                }
+                else {
+                    NamespaceConstraint::Not(namespaces)
+                }
+            },
+            (NamespaceConstraint::Enumeration(e), NamespaceConstraint::Not(n)) |
+            (NamespaceConstraint::Not(n), NamespaceConstraint::Enumeration(e)) => {
+                let namespaces: Vec<_> = n.iter().filter(|ns| !e.contains(ns)).cloned().collect();
+                if namespaces.is_empty() {
+                    NamespaceConstraint::Any
+                }
+                else {
+                    NamespaceConstraint::Not(namespaces)
+                }
+            },
+        };
+        let not_qnames = self.not_qnames.iter().filter(|n| other.not_qnames.contains(n)).cloned().collect();
+        Wildcard {
+            namespaces,
+            not_qnames,
+            not_defined: self.not_defined && other.not_defined,
+            process_contents: other.process_contents,
+        }
+    }
+
+    pub fn allows_namespace(&self, namespace: Option<&'input str>) -> bool {
+        match self.namespaces {
+            NamespaceConstraint::Any => true,
+            NamespaceConstraint::Enumeration(ref namespaces) => namespaces.contains(&namespace),
+            NamespaceConstraint::Not(ref namespaces) => !namespaces.contains(&namespace),
+        }
+    }
+
+    /// Whether every namespace allowed by this wildcard is allowed by the
+    /// other one.
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-ns-subset
+    pub fn is_subset(&self, other: &Wildcard<'input>) -> bool {
+        match (&self.namespaces, &other.namespaces) {
+            (_, NamespaceConstraint::Any) => true,
+            (NamespaceConstraint::Any, _) => false,
+            (NamespaceConstraint::Enumeration(e1), NamespaceConstraint::Enumeration(e2)) =>
+                e1.iter().all(|ns| e2.contains(ns)),
+            (NamespaceConstraint::Enumeration(e), NamespaceConstraint::Not(n)) =>
+                e.iter().all(|ns| !n.contains(ns)),
+            (NamespaceConstraint::Not(n1), NamespaceConstraint::Not(n2)) =>
+                n2.iter().all(|ns| n1.contains(ns)),
+            (NamespaceConstraint::Not(_), NamespaceConstraint::Enumeration(_)) => false,
+        }
+    }
+
+    /// Whether some namespace is allowed by both wildcards.
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-aw-intersect
+    pub fn overlaps(&self, other: &Wildcard<'input>) -> bool {
+        match (&self.namespaces, &other.namespaces) {
+            (NamespaceConstraint::Any, _) | (_, NamespaceConstraint::Any) => true,
+            (NamespaceConstraint::Enumeration(e1), NamespaceConstraint::Enumeration(e2)) =>
+                e1.iter().any(|ns| e2.contains(ns)),
+            (NamespaceConstraint::Not(_), NamespaceConstraint::Not(_)) => true,
+            (NamespaceConstraint::Enumeration(e), NamespaceConstraint::Not(n)) |
+            (NamespaceConstraint::Not(n), NamespaceConstraint::Enumeration(e)) =>
+                e.iter().any(|ns| !n.contains(ns)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OpenContentMode {
+    Interleave,
+    Suffix,
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#oc
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OpenContent<'input> {
+    pub mode: OpenContentMode,
+    pub wildcard: Wildcard<'input>,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DerivationMethod {
+    Extension,
+    Restriction,
+    List,
+    Union,
+}
+
+/// Value of the final, block, finalDefault and blockDefault attributes.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DerivationSet {
+    pub extension: bool,
+    pub restriction: bool,
+    pub list: bool,
+    pub union: bool,
+    pub substitution: bool,
+}
+impl DerivationSet {
+    fn from_tokens<'a, I: Iterator<Item=&'a str>>(tokens: I) -> DerivationSet {
+        let mut set = DerivationSet::default();
+        for token in tokens {
+            match token {
+                "#all" => return DerivationSet { extension: true, restriction: true, list: true, union: true, substitution: true },
+                "extension" => set.extension = true,
+                "restriction" => set.restriction = true,
+                "list" => set.list = true,
+                "union" => set.union = true,
+                "substitution" => set.substitution = true,
+                _ => panic!("Unknown derivation method: {}", token),
+            }
+        }
+        set
+    }
+
+    pub fn contains(&self, method: DerivationMethod) -> bool {
+        match method {
+            DerivationMethod::Extension => self.extension,
+            DerivationMethod::Restriction => self.restriction,
+            DerivationMethod::List => self.list,
+            DerivationMethod::Union => self.union,
+        }
+    }
+
+    pub fn intersects(&self, other: &DerivationSet) -> bool {
+        (self.extension && other.extension) ||
+        (self.restriction && other.restriction) ||
+        (self.list && other.list) ||
+        (self.union && other.union) ||
+        (self.substitution && other.substitution)
+    }
+
+    pub fn union(&self, other: &DerivationSet) -> DerivationSet {
+        DerivationSet {
+            extension: self.extension || other.extension,
+            restriction: self.restriction || other.restriction,
+            list: self.list || other.list,
+            union: self.union || other.union,
+            substitution: self.substitution || other.substitution,
+        }
+    }
+}
+
+fn process_block_set<'input>(set: &xs::BlockSet<'input>) -> DerivationSet {
+    match set {
+        unions::UnionTokenDerivationControlList::Token(restrictions::All(Token(token))) =>
+            DerivationSet::from_tokens(Some(*token).into_iter()),
+        unions::UnionTokenDerivationControlList::DerivationControlList(list) =>
+            DerivationSet::from_tokens(list.0.iter().map(|item| ((item.0).0).0)),
     }
 }
 
+/// https://www.w3.org/TR/xmlschema11-2/#element-simpleType
+fn process_simple_derivation_set<'input>(set: &xs::SimpleDerivationSet<'input>) -> DerivationSet {
+    match set {
+        unions::UnionTokenDerivationControlList::Token(restrictions::All(Token(_))) =>
+            DerivationSet { extension: true, restriction: true, list: true, union: true, substitution: false },
+        unions::UnionTokenDerivationControlList::DerivationControlList(list) =>
+            DerivationSet::from_tokens(list.0.iter().map(|item| {
+                match ((item.0).0).0 {
+                    "substitution" => panic!("substitution is not a derivation method of simple types."),
+                    token => token,
+                }
+            })),
+    }
+}
+
+fn process_derivation_set<'input>(set: &xs::DerivationSet<'input>) -> DerivationSet {
+    match set {
+        unions::UnionTokenReducedDerivationControlList::Token(restrictions::All(Token(token))) =>
+            DerivationSet::from_tokens(Some(*token).into_iter()),
+        unions::UnionTokenReducedDerivationControlList::ReducedDerivationControlList(list) =>
+            DerivationSet::from_tokens(list.0.iter().map(|item| ((item.0).0).0)),
+    }
+}
+
+fn process_full_derivation_set<'input>(set: &xs::FullDerivationSet<'input>) -> DerivationSet {
+    match set {
+        unions::UnionTokenTypeDerivationControlList::Token(restrictions::All(Token(token))) =>
+            DerivationSet::from_tokens(Some(*token).into_iter()),
+        unions::UnionTokenTypeDerivationControlList::TypeDerivationControlList(list) =>
+            DerivationSet::from_tokens(list.0.iter().map(|item| ((item.0).0).0)),
+    }
+}
+
+/// Returns the methods used to derive a type from one of its ancestors,
+/// or `None` if the derivation chain is not in `derivations`.
+pub fn derivation_methods<'input>(derivations: &[(Option<FullName<'input>>, FullName<'input>, DerivationMethod)], type_: &FullName<'input>, ancestor: &FullName<'input>) -> Option<DerivationSet> {
+    let mut methods = DerivationSet::default();
+    let mut current = *type_;
+    let mut seen = HashSet::new();
+    while current != *ancestor {
+        if !seen.insert(current) {
+            panic!("Circular derivation of {:?}", current);
+        }
+        let derivation = derivations.iter().find(|&&(ref derived, _, method)| {
+            *derived == Some(current) && (method == DerivationMethod::Extension || method == DerivationMethod::Restriction)
+        });
+        match derivation {
+            Some(&(_, base, DerivationMethod::Extension)) => { methods.extension = true; current = base },
+            Some(&(_, base, _)) => { methods.restriction = true; current = base },
+            None => return None,
+        }
+    }
+    Some(methods)
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RichType<'input, T: Debug + Hash + PartialEq + Eq + PartialOrd + Ord> {
     pub name_hint: NameHint<'input>,
@@ -107,7 +347,7 @@ impl<'input> RichType<'input, SimpleType<'input>> {
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Type<'input> {
-    Any,
+    Any(Wildcard<'input>),
     Empty,
     Alias(FullName<'input>),
     Extension(FullName<'input>, Box<RichType<'input, Type<'input>>>),
@@ -120,6 +360,9 @@ pub enum Type<'input> {
     Sequence(usize, usize, String),
     InlineSequence(Vec<RichType<'input, Type<'input>>>),
     Simple(SimpleType<'input>),
+    OpenContent(OpenContent<'input>, Box<RichType<'input, Type<'input>>>),
+    /// Content type whose character data is kept between the children.
+    Mixed(Box<RichType<'input, Type<'input>>>),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -143,33 +386,67 @@ pub struct Processor<'ast, 'input: 'ast> {
     pub choices: HashMap<Vec<RichType<'input, Type<'input>>>, HashSet<String>>,
     pub sequences: HashMap<Vec<RichType<'input, Type<'input>>>, (HashSet<String>, Documentation<'input>)>,
     pub groups: HashMap<FullName<'input>, RichType<'input, Type<'input>>>,
+    pub attributes: HashMap<FullName<'input>, Option<SimpleType<'input>>>,
     pub attribute_groups: HashMap<FullName<'input>, Attrs<'input>>,
     pub inline_elements: HashMap<(Option<&'input str>, &'input str, Attrs<'input>, Type<'input>), (HashSet<String>, Documentation<'input>)>,
 
     pub lists: HashMap<RichType<'input, SimpleType<'input>>, HashSet<String>>,
     pub unions: HashMap<Vec<RichType<'input, SimpleType<'input>>>, HashSet<String>>,
     pub simple_restrictions: HashSet<(FullName<'input>, Facets<'input>)>,
+    /// Names of the facets declared `fixed="true"` by each restriction
+    /// in `simple_restrictions`.
+    pub fixed_facets: HashMap<(FullName<'input>, Facets<'input>), HashSet<&'static str>>,
     pub substitution_groups: HashMap<FullName<'input>, Vec<FullName<'input>>>,
+    /// Public and system identifiers of notation declarations.
+    pub notations: HashMap<FullName<'input>, (Option<&'input str>, Option<&'input str>)>,
+    pub wildcards: HashSet<Wildcard<'input>>,
+    /// <defaultOpenContent>, and whether it applies to empty content types.
+    pub default_open_content: Option<(OpenContent<'input>, bool)>,
+    pub final_default: DerivationSet,
+    pub block_default: DerivationSet,
+    pub abstract_elements: HashSet<FullName<'input>>,
+    pub abstract_types: HashSet<FullName<'input>>,
+    /// {substitution group exclusions} of top-level elements
+    pub element_finals: HashMap<FullName<'input>, DerivationSet>,
+    /// {disallowed substitutions} of top-level elements
+    pub element_blocks: HashMap<FullName<'input>, DerivationSet>,
+    pub type_finals: HashMap<FullName<'input>, DerivationSet>,
+    /// {prohibited substitutions} of complex types
+    pub type_blocks: HashMap<FullName<'input>, DerivationSet>,
+    /// (derived type, base type, method). Anonymous types of top-level
+    /// elements are named after their element, like in `types`.
+    pub derivations: Vec<(Option<FullName<'input>>, FullName<'input>, DerivationMethod)>,
     _phantom: PhantomData<&'ast ()>, // Sometimes I need 'ast when prototyping
 }
 
 impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
     pub fn new(ast: &'ast xs::Schema<'input>) -> Processor<'ast, 'input> {
         let target_namespace = ast.attr_target_namespace.as_ref().map(|t| t.0);
-        let element_form_default_qualified = match ast.attr_element_form_default.as_ref().map(|x| ((x.0).0).0) {
+        let element_form_default_qualified = match ast.attr_element_form_default.as_ref().map(|x| (x.0).0) {
             Some("qualified") => true,
             Some("unqualified") | None => false,
             _ => unreachable!(),
         };
-        let attribute_form_default_qualified = match ast.attr_attribute_form_default.as_ref().map(|x| ((x.0).0).0) {
+        let attribute_form_default_qualified = match ast.attr_attribute_form_default.as_ref().map(|x| (x.0).0) {
             Some("qualified") => true,
             Some("unqualified") | None => false,
             _ => unreachable!(),
         };
+        let final_default = ast.attr_final_default.as_ref().map(process_full_derivation_set).unwrap_or_default();
+        let block_default = ast.attr_block_default.as_ref().map(process_block_set).unwrap_or_default();
         Processor {
             target_namespace,
             element_form_default_qualified,
             attribute_form_default_qualified,
+            final_default,
+            block_default,
+            abstract_elements: HashSet::new(),
+            abstract_types: HashSet::new(),
+            element_finals: HashMap::new(),
+            element_blocks: HashMap::new(),
+            type_finals: HashMap::new(),
+            type_blocks: HashMap::new(),
+            derivations: Vec::new(),
             elements: HashMap::new(),
             types: HashMap::new(),
             groups: HashMap::new(),
@@ -177,28 +454,178 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             lists: HashMap::new(),
             unions: HashMap::new(),
             sequences: HashMap::new(),
+            attributes: HashMap::new(),
             attribute_groups: HashMap::new(),
             inline_elements: HashMap::new(),
             simple_types: HashMap::new(),
             simple_restrictions: HashSet::new(),
+            fixed_facets: HashMap::new(),
             substitution_groups: HashMap::new(),
+            notations: HashMap::new(),
+            wildcards: HashSet::new(),
+            default_open_content: None,
             _phantom: PhantomData::default(),
         }
     }
 
     pub fn process_ast(&mut self, ast: &'ast xs::Schema<'input>) {
+        if let Some(ref open_content) = ast.open_content {
+            self.process_default_open_content(&open_content.default_open_content);
+        }
         for top_level_item in ast.sequence_schema_top_annotation.iter() {
             match top_level_item.schema_top {
                 xs::SchemaTop::Redefinable(ref r) => self.process_redefinable(r, false),
                 xs::SchemaTop::Element(ref e) => { self.process_toplevel_element(e); },
-                xs::SchemaTop::Attribute(_) => unimplemented!("top-level attribute"),
+                xs::SchemaTop::Attribute(ref e) => self.process_toplevel_attribute(e),
                 xs::SchemaTop::Notation(ref e) => self.process_notation(e),
             }
         }
+        self.check_derivations();
+        self.check_restrictions();
+        self.check_simple_restrictions();
     }
 
+    /// Name of the type of a top-level element, as used in `derivations`.
+    pub fn element_type_name(&self, element: &FullName<'input>) -> Option<FullName<'input>> {
+        match self.elements.get(element).map(|e| &e.type_) {
+            Some(Type::Alias(name)) => Some(*name),
+            Some(Type::Empty) => Some(FullName::new(Some(SCHEMA_URI), "anyType")),
+            Some(_) => Some(*element),
+            None => None,
+        }
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-ct-extends
+    /// https://www.w3.org/TR/xmlschema11-1/#derivation-ok-restriction
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-equiv-derived-ok-rec
+    fn check_derivations(&self) {
+        for &(ref derived, ref base, method) in self.derivations.iter() {
+            if let Some(final_) = self.type_finals.get(base) {
+                if final_.contains(method) {
+                    panic!("Type {:?} cannot be derived by {:?} from {:?}, whose final is {:?}.", derived, method, base, final_);
+                }
+            }
+        }
+        for (head, members) in self.substitution_groups.iter() {
+            let final_ = match self.element_finals.get(head) {
+                Some(final_) => final_,
+                None => continue, // Declared in an other schema
+            };
+            let head_type = match self.element_type_name(head) {
+                Some(head_type) => head_type,
+                None => continue,
+            };
+            for member in members {
+                let methods = self.element_type_name(member)
+                    .and_then(|member_type| derivation_methods(&self.derivations, &member_type, &head_type));
+                if let Some(methods) = methods {
+                    if methods.intersects(final_) {
+                        panic!("{:?} cannot be in the substitution group of {:?}, whose final is {:?}.", member, head, final_);
+                    }
+                }
+            }
+        }
+    }
+
+    fn process_default_open_content(&mut self, default_open_content: &'ast xs::DefaultOpenContent<'input>) {
+        let xs::DefaultOpenContent { ref attrs, ref attr_id, ref attr_applies_to_empty, ref attr_mode, ref annotation, ref any_wildcard } = default_open_content;
+        let mode = match attr_mode.as_ref().map(|x| (x.0).0) {
+            Some("interleave") | None => OpenContentMode::Interleave,
+            Some("suffix") => OpenContentMode::Suffix,
+            _ => unreachable!(),
+        };
+        let wildcard = self.process_any_wildcard(any_wildcard);
+        let applies_to_empty = attr_applies_to_empty.as_ref().map(|b| b.0).unwrap_or(false);
+        self.default_open_content = Some((OpenContent { mode, wildcard }, applies_to_empty));
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-1/#dcl.ctd.ctcc.common
+    ///
+    /// Returns `None` for `<openContent mode="none">`.
+    fn process_open_content(&mut self, open_content: &'ast xs::OpenContent<'input>) -> Option<OpenContent<'input>> {
+        let xs::OpenContent { ref attrs, ref attr_id, ref attr_mode, ref annotation, ref any_wildcard } = open_content;
+        let mode = match attr_mode.as_ref().map(|x| (x.0).0) {
+            Some("none") => return None,
+            Some("interleave") | None => OpenContentMode::Interleave,
+            Some("suffix") => OpenContentMode::Suffix,
+            _ => unreachable!(),
+        };
+        let any_wildcard = any_wildcard.as_ref().expect("<openContent> has no <any> child.");
+        let wildcard = self.process_any_wildcard(any_wildcard);
+        Some(OpenContent { mode, wildcard })
+    }
+
+    fn process_any_wildcard(&mut self, any_wildcard: &'ast inline_elements::AnyWildcard<'input>) -> Wildcard<'input> {
+        let inline_elements::AnyWildcard { ref attrs, ref attr_id, ref attr_namespace, ref attr_not_namespace, ref attr_process_contents, ref annotation } = any_wildcard;
+        let wildcard = self.process_wildcard(attr_namespace, attr_not_namespace, &None, attr_process_contents);
+        self.wildcards.insert(wildcard.clone());
+        wildcard
+    }
+
+    /// Wraps the content type of a complex type in its open content, be it
+    /// explicit or the schema's default one.
+    fn add_open_content(&mut self,
+            ty: RichType<'input, Type<'input>>,
+            open_content: Option<&'ast xs::OpenContent<'input>>,
+            ) -> RichType<'input, Type<'input>> {
+        let open_content = match open_content {
+            Some(open_content) => self.process_open_content(open_content),
+            None => {
+                match self.default_open_content {
+                    Some((_, false)) if ty.type_ == Type::Empty => None,
+                    Some((ref open_content, _)) => Some(open_content.clone()),
+                    None => None,
+                }
+            },
+        };
+        match open_content {
+            Some(open_content) => {
+                let RichType { name_hint, attrs, type_, doc } = ty;
+                let inner = RichType { name_hint: name_hint.clone(), attrs, type_, doc: Documentation::new() };
+                RichType::new(name_hint, Type::OpenContent(open_content, Box::new(inner)), doc)
+            },
+            None => ty,
+        }
+    }
+
+    /// Content type of xs:anyType: any attributes, and any character data
+    /// and elements, which are parsed laxly.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-1/#builtin-ctd
+    fn any_type(&mut self, doc: Documentation<'input>) -> RichType<'input, Type<'input>> {
+        let wildcard = Wildcard {
+            namespaces: NamespaceConstraint::Any,
+            not_qnames: Vec::new(),
+            not_defined: false,
+            process_contents: ProcessContents::Lax,
+        };
+        let mut ty = self.wildcard_particle(wildcard.clone(), 0, usize::max_value(), doc);
+        ty.attrs.any_attributes = Some(wildcard);
+        self.add_mixed(ty, true)
+    }
+
+    /// Marks the content type of a complex type as mixed.
+    fn add_mixed(&self, ty: RichType<'input, Type<'input>>, mixed: bool) -> RichType<'input, Type<'input>> {
+        if !mixed {
+            return ty;
+        }
+        let RichType { name_hint, attrs, type_, doc } = ty;
+        let inner = RichType { name_hint: name_hint.clone(), attrs: Attrs::new(), type_, doc: Documentation::new() };
+        RichType { name_hint, attrs, type_: Type::Mixed(Box::new(inner)), doc }
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-1/#declare-notation
     fn process_notation(&mut self, notation: &'ast xs::Notation<'input>) {
-        // TODO
+        let xs::Notation { ref attrs, ref attr_id, ref attr_name, ref attr_public, ref attr_system, ref annotation } = notation;
+        let name = FullName::new(self.target_namespace, attr_name.0);
+        let public = attr_public.as_ref().map(|public| (public.0).0);
+        let system = attr_system.as_ref().map(|system| system.0);
+        if public.is_none() && system.is_none() {
+            panic!("Notation {} has neither a public nor a system identifier.", name);
+        }
+        if self.notations.insert(name, (public, system)).is_some() {
+            panic!("Duplicate notation declaration {}.", name);
+        }
     }
 
     fn process_redefinable(&mut self, r: &'ast xs::Redefinable<'input>, inlinable: bool) {
@@ -216,14 +643,19 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                 match c {
                     enums::AnnotationContent::Appinfo(_) => None,
                     enums::AnnotationContent::Documentation(e) => {
-                        let xs::Documentation { ref attrs, ref attr_source, ref sequence_any } = **e;
-                        Some(sequence_any.iter().flat_map(|sequences::SequenceAny { any }| {
-                            any.0.iter().filter_map(|tok| {
+                        let xs::Documentation { ref attrs, ref attr_source, ref attr_lang, ref mixed } = **e;
+                        Some(mixed.iter().flat_map(|item| {
+                            let tokens = match item {
+                                MixedItem::Text(s) => return vec![*s],
+                                MixedItem::Other(ref any) => &any.0[..],
+                                MixedItem::Element(_) => &[][..],
+                            };
+                            tokens.iter().filter_map(|tok| {
                                 match tok {
                                     XmlToken::Text(s) => Some(s.to_str()),
                                     _ => None,
                                 }
-                            })
+                            }).collect()
                         }))
                     },
                 }
@@ -253,6 +685,8 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             ) -> RichType<'input, Type<'input>> {
         let xs::Group { ref attrs, ref attr_id, ref attr_name, ref annotation, choice_all_choice_sequence: ref content } = group;
         let name = attr_name;
+        // Occurrence bounds are on the references to the group
+        // (see process_group_ref), not on its definition.
         let max_occurs = 1;
         let min_occurs = 1;
 
@@ -287,6 +721,7 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         let inline_elements::LocalSimpleType { ref attrs, ref attr_id, ref annotation, ref simple_derivation } = simple_type;
         //let struct_name = self.namespaces.new_type(QName::from(name));
         let annotation: Vec<_> = annotation.iter().collect();
+        self.add_simple_derivation(None, simple_derivation);
         match simple_derivation {
             xs::SimpleDerivation::Restriction(e) => self.process_simple_restriction(e, annotation.clone()),
             xs::SimpleDerivation::List(ref e) => self.process_list(e, annotation.clone()),
@@ -294,6 +729,29 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         }
     }
 
+    fn add_simple_derivation(&mut self, name: Option<FullName<'input>>, simple_derivation: &'ast xs::SimpleDerivation<'input>) {
+        match simple_derivation {
+            xs::SimpleDerivation::Restriction(e) => {
+                if let Some(ref base) = e.attr_base {
+                    let base = FullName::from_qname(base, self.target_namespace);
+                    self.derivations.push((name, base, DerivationMethod::Restriction));
+                }
+            },
+            xs::SimpleDerivation::List(e) => {
+                if let Some(ref item_type) = e.attr_item_type {
+                    let item_type = FullName::from_qname(item_type, self.target_namespace);
+                    self.derivations.push((name, item_type, DerivationMethod::List));
+                }
+            },
+            xs::SimpleDerivation::Union(e) => {
+                for member_type in e.attr_member_types.iter().flat_map(|l| l.0.iter()) {
+                    let member_type = FullName::from_qname(member_type, self.target_namespace);
+                    self.derivations.push((name, member_type, DerivationMethod::Union));
+                }
+            },
+        }
+    }
+
     fn process_simple_type(&mut self,
             simple_type: &'ast xs::SimpleType<'input>,
             ) -> RichType<'input, SimpleType<'input>> {
@@ -301,6 +759,8 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         let annotation: Vec<_> = annotation.iter().collect();
         let name = attr_name;
         let name = FullName::new(self.target_namespace, name.0);
+        self.type_finals.insert(name, attr_final.as_ref().map(process_simple_derivation_set).unwrap_or(self.final_default));
+        self.add_simple_derivation(Some(name), simple_derivation);
         //let struct_name = self.namespaces.new_type(QName::from(name));
         let ty = match simple_derivation {
             xs::SimpleDerivation::Restriction(e) => 
@@ -397,14 +857,21 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             ) -> RichType<'input, Type<'input>> {
         let xs::ComplexType { ref attrs, ref attr_id, ref attr_name, ref attr_mixed, ref attr_abstract, ref attr_final, ref attr_block, ref attr_default_attributes_apply, ref annotation, ref complex_type_model } = complex_type;
         let name = attr_name;
+        let full_name = FullName::new(self.target_namespace, name.0);
+        if attr_abstract.as_ref().map(|b| b.0).unwrap_or(false) {
+            self.abstract_types.insert(full_name);
+        }
+        self.type_finals.insert(full_name, attr_final.as_ref().map(process_derivation_set).unwrap_or(self.final_default));
+        self.type_blocks.insert(full_name, attr_block.as_ref().map(process_derivation_set).unwrap_or(self.block_default));
         //let struct_name = self.namespaces.new_type(QName::from(name));
-        let mut ty = match complex_type_model {
+        let ty = match complex_type_model {
             xs::ComplexTypeModel::SimpleContent(_) => unimplemented!("simpleContent"),
             xs::ComplexTypeModel::ComplexContent(ref model) =>
-                self.process_complex_content(model, false),
+                self.process_complex_content(model, Some(full_name), false),
             xs::ComplexTypeModel::CompleteContentModel { ref open_content, ref type_def_particle, ref attr_decls, ref assertions } =>
                 self.process_complete_content_model(open_content, type_def_particle, attr_decls, assertions, inlinable),
         };
+        let mut ty = self.add_mixed(ty, is_mixed(attr_mixed, complex_type_model));
         ty.doc.extend(&self.process_annotation(&annotation.iter().collect()));
 
         let doc = ty.doc.clone();
@@ -425,14 +892,16 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             ) -> RichType<'input, Type<'input>> {
         let inline_elements::LocalComplexType { ref attrs, ref attr_id, ref attr_mixed, ref attr_default_attributes_apply, annotation: ref annotation2, ref complex_type_model } = complex_type;
         let name = attr_name;
+        let full_name = name.map(|name| FullName::new(self.target_namespace, name.0));
         //let struct_name = self.namespaces.new_type(QName::from(name));
-        let mut ty = match complex_type_model {
+        let ty = match complex_type_model {
             xs::ComplexTypeModel::SimpleContent(_) => unimplemented!("simpleContent"),
             xs::ComplexTypeModel::ComplexContent(ref model) =>
-                self.process_complex_content(model, false),
+                self.process_complex_content(model, full_name, false),
             xs::ComplexTypeModel::CompleteContentModel { ref open_content, ref type_def_particle, ref attr_decls, ref assertions } =>
                 self.process_complete_content_model(open_content, type_def_particle, attr_decls, assertions, inlinable),
         };
+        let mut ty = self.add_mixed(ty, is_mixed(attr_mixed, complex_type_model));
         ty.doc.extend(&self.process_annotation(&vec_concat_opt(&annotation, annotation2.as_ref())));
 
         if let Some(name) = name {
@@ -465,10 +934,11 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                 Documentation::new()
             ),
         };
+        let ty = self.add_open_content(ty, open_content.as_ref().map(|b| &**b));
         ty.add_attrs(self.process_attr_decls(attr_decls))
     }
 
-    fn process_complex_content(&mut self, model: &'ast xs::ComplexContent<'input>, inlinable: bool) -> RichType<'input, Type<'input>> {
+    fn process_complex_content(&mut self, model: &'ast xs::ComplexContent<'input>, name: Option<FullName<'input>>, inlinable: bool) -> RichType<'input, Type<'input>> {
         let xs::ComplexContent { ref attrs, ref attr_id, ref attr_mixed, ref annotation, ref choice_restriction_extension } = model;
         let annotation = annotation.iter().collect();
         match choice_restriction_extension {
@@ -478,17 +948,20 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                     ref sequence_open_content_type_def_particle,
                     ref attr_decls, ref assertions
                 } = **r;
-                let ty = match sequence_open_content_type_def_particle {
+                let base = FullName::from_qname(attr_base, self.target_namespace);
+                self.derivations.push((name, base, DerivationMethod::Restriction));
+                let (ty, open_content) = match sequence_open_content_type_def_particle {
                     Some(sequences::SequenceOpenContentTypeDefParticle { open_content, type_def_particle }) =>
-                        self.process_complex_restriction(attr_base, type_def_particle, vec_concat_opt(&annotation, annotation2.as_ref())),
+                        (self.process_complex_restriction(attr_base, type_def_particle, vec_concat_opt(&annotation, annotation2.as_ref())), open_content.as_ref()),
                     None => {
-                        RichType::new(
+                        (RichType::new(
                             NameHint::new("empty_extension"),
                             Type::Empty,
                             self.process_annotation(&vec_concat_opt(&annotation, annotation2.as_ref())),
-                            )
+                            ), None)
                     },
                 };
+                let ty = self.add_open_content(ty, open_content);
                 ty.add_attrs(self.process_attr_decls(attr_decls))
             },
             enums::ChoiceRestrictionExtension::Extension(ref e) => {
@@ -496,9 +969,14 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                     ref attrs, ref attr_base, ref attr_id, annotation: ref annotation2, ref open_content,
                     ref type_def_particle, ref attr_decls, ref assertions
                 } = **e;
+                let base = FullName::from_qname(attr_base, self.target_namespace);
+                self.derivations.push((name, base, DerivationMethod::Extension));
                 let ty = match type_def_particle {
-                    Some(type_def_particle) =>
-                        self.process_extension(attrs, attr_base, type_def_particle, vec_concat_opt(&annotation, annotation2.as_ref()), inlinable),
+                    Some(type_def_particle) => {
+                        let ty = self.process_extension(attrs, attr_base, type_def_particle, vec_concat_opt(&annotation, annotation2.as_ref()), inlinable);
+                        self.add_open_content(ty, open_content.as_ref())
+                    },
+                    // The content type is the base's, and so is its open content.
                     None => self.process_trivial_extension(attrs, attr_base, vec_concat_opt(&annotation, annotation2.as_ref())),
                 };
                 ty.add_attrs(self.process_attr_decls(attr_decls))
@@ -521,34 +999,78 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             )
     }
     
-    fn process_facets(&mut self, facet_list: &Vec<enums::ChoiceFacetAny<'input>>) -> Facets<'input> {
+    /// Returns the facets, and the names of the ones declared fixed.
+    fn process_facets(&mut self, facet_list: &Vec<enums::ChoiceFacetAny<'input>>) -> (Facets<'input>, HashSet<&'static str>) {
         let mut facets = Facets::default();
+        let mut fixed = HashSet::new();
+        let mut set_fixed = |name, attr_fixed: &Option<Boolean>| {
+            if let Some(Boolean(true, _)) = attr_fixed {
+                fixed.insert(name);
+            }
+        };
         use parser::xs::Facet::*;
         for facet_or_any in facet_list {
             match facet_or_any {
                 enums::ChoiceFacetAny::Facet(e) => {
                     match **e {
-                        FacetHead(_) => panic!("abstract element"),
-                        MinExclusive(ref e) => facets.min_exclusive = Some(e.attr_value.0.parse().expect("invalid minexclusive")),
-                        MinInclusive(ref e) => facets.min_inclusive = Some(e.attr_value.0.parse().expect("invalid mininclusive")),
-                        MaxExclusive(ref e) => facets.max_exclusive = Some(e.attr_value.0.parse().expect("invalid maxexclusive")),
-                        MaxInclusive(ref e) => facets.max_inclusive = Some(e.attr_value.0.parse().expect("invalid maxinclusive")),
-                        TotalDigits(ref e) => facets.total_digits = Some(e.attr_value.0),
-                        FractionDigits(ref e) => facets.fraction_digits = Some(e.attr_value.0),
-                        Length(ref e) => facets.length = Some(e.attr_value.0 as usize),
-                        MinLength(ref e) => facets.min_length = Some(e.attr_value.0 as usize),
-                        MaxLength(ref e) => facets.max_length = Some(e.attr_value.0 as usize),
+                        MinExclusive(ref e) => {
+                            facets.min_exclusive = Some(e.attr_value.0.parse().expect("invalid minexclusive"));
+                            set_fixed("minExclusive", &e.attr_fixed);
+                        },
+                        MinInclusive(ref e) => {
+                            facets.min_inclusive = Some(e.attr_value.0.parse().expect("invalid mininclusive"));
+                            set_fixed("minInclusive", &e.attr_fixed);
+                        },
+                        MaxExclusive(ref e) => {
+                            facets.max_exclusive = Some(e.attr_value.0.parse().expect("invalid maxexclusive"));
+                            set_fixed("maxExclusive", &e.attr_fixed);
+                        },
+                        MaxInclusive(ref e) => {
+                            facets.max_inclusive = Some(e.attr_value.0.parse().expect("invalid maxinclusive"));
+                            set_fixed("maxInclusive", &e.attr_fixed);
+                        },
+                        TotalDigits(ref e) => {
+                            facets.total_digits = Some(e.attr_value.0.to_u64().unwrap_or(u64::max_value()));
+                            set_fixed("totalDigits", &e.attr_fixed);
+                        },
+                        FractionDigits(ref e) => {
+                            facets.fraction_digits = Some(e.attr_value.0.to_u64().unwrap_or(u64::max_value()));
+                            set_fixed("fractionDigits", &e.attr_fixed);
+                        },
+                        Length(ref e) => {
+                            facets.length = Some(e.attr_value.to_usize_saturating());
+                            set_fixed("length", &e.attr_fixed);
+                        },
+                        MinLength(ref e) => {
+                            facets.min_length = Some(e.attr_value.to_usize_saturating());
+                            set_fixed("minLength", &e.attr_fixed);
+                        },
+                        MaxLength(ref e) => {
+                            facets.max_length = Some(e.attr_value.to_usize_saturating());
+                            set_fixed("maxLength", &e.attr_fixed);
+                        },
                         Enumeration(ref e) => facets.enumeration.get_or_insert(Vec::new()).push(e.attr_value.0),
-                        WhiteSpace(ref e) => facets.white_space = Some(((e.attr_value.0).0).0),
-                        Pattern(ref e) => facets.pattern = Some(e.attr_value.0),
+                        WhiteSpace(ref e) => {
+                            facets.white_space = Some((e.attr_value.0).0);
+                            set_fixed("whiteSpace", &e.attr_fixed);
+                        },
+                        Pattern(ref e) => {
+                            if let Err(err) = patterns::compile(e.attr_value.0) {
+                                panic!("Invalid pattern {:?}: {}", e.attr_value.0, err);
+                            }
+                            facets.pattern.get_or_insert(Vec::new()).push(e.attr_value.0);
+                        },
                         Assertion(ref e) => unimplemented!("assertion facet"),
-                        ExplicitTimezone(ref e) => facets.explicit_timezone = Some(((e.attr_value.0).0).0),
+                        ExplicitTimezone(ref e) => {
+                            facets.explicit_timezone = Some((e.attr_value.0).0);
+                            set_fixed("explicitTimezone", &e.attr_fixed);
+                        },
                     };
                 },
                 enums::ChoiceFacetAny::Any(_) => (), // TODO (probably just whitespaces)
             }
         }
-        facets
+        (facets, fixed)
     }
 
     fn process_simple_restriction(&mut self, 
@@ -559,11 +1081,18 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         let base = attr_base;
         let base = base.unwrap_or(QName { namespace: Some(SCHEMA_URI), local_name: "anySimpleType" });
         let xs::SimpleRestrictionModel { ref local_simple_type, ref choice_facet_any } = simple_restriction_model;
-        let facets = self.process_facets(choice_facet_any);
+        let (facets, fixed_facets) = self.process_facets(choice_facet_any);
 
         let base = FullName::from_qname(&base, self.target_namespace);
+        // https://www.w3.org/TR/xmlschema11-2/#enumeration-required-notation
+        if base == FullName::new(Some(SCHEMA_URI), "NOTATION") && facets.enumeration.is_none() {
+            panic!("Restriction of NOTATION without an enumeration facet.");
+        }
 
         self.simple_restrictions.insert((base, facets.clone()));
+        if !fixed_facets.is_empty() {
+            self.fixed_facets.entry((base, facets.clone())).or_insert_with(HashSet::new).extend(fixed_facets);
+        }
 
         match local_simple_type {
             Some(inline_elements::LocalSimpleType { ref attrs, ref attr_id, annotation: ref annotation2, ref simple_derivation }) => {
@@ -613,13 +1142,116 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             any: &'ast xs::Any<'input>,
             annotation: Vec<&'ast xs::Annotation<'input>>,
             ) -> RichType<'input, Type<'input>> {
+        let xs::Any { ref attrs, ref attr_id, ref attr_not_q_name, ref attr_namespace, ref attr_not_namespace, ref attr_process_contents, ref attr_min_occurs, ref attr_max_occurs, annotation: ref annotation2 } = any;
+        let wildcard = self.process_wildcard(attr_namespace, attr_not_namespace, attr_not_q_name, attr_process_contents);
+        let min_occurs = parse_min_occurs(attr_min_occurs);
+        let max_occurs = parse_max_occurs(attr_max_occurs);
+        let doc = self.process_annotation(&annotation.iter().cloned().chain(annotation2.iter()).collect());
+        self.wildcard_particle(wildcard, min_occurs, max_occurs, doc)
+    }
+
+    fn wildcard_particle(&mut self,
+            wildcard: Wildcard<'input>,
+            min_occurs: usize,
+            max_occurs: usize,
+            doc: Documentation<'input>,
+            ) -> RichType<'input, Type<'input>> {
+        self.wildcards.insert(wildcard.clone());
+        let name_hint = NameHint::new("any");
+        if min_occurs == 1 && max_occurs == 1 {
+            return RichType::new(name_hint, Type::Any(wildcard), doc);
+        }
+        // Wildcards have no occurrence bounds of their own, so they are
+        // wrapped in a sequence, like particles of single-item choices.
+        let name = name_from_hint(&name_hint).unwrap();
+        let items = vec![RichType::new(name_hint.clone(), Type::Any(wildcard), doc.clone())];
+        let (names, docs) = self.sequences.entry(items)
+            .or_insert((HashSet::new(), Documentation::new()));
+        names.insert(name.clone());
+        docs.extend(&doc);
         RichType::new(
-            NameHint::new("any"),
-            Type::Any,
-            self.process_annotation(&annotation),
+            name_hint,
+            Type::Sequence(min_occurs, max_occurs, name),
+            doc,
             )
     }
 
+    fn process_namespace_list(&self, list: &lists::UnionAnyUriTokenList<'input>) -> Vec<Option<&'input str>> {
+        list.0.iter().map(|item| {
+            match item {
+                unions::UnionAnyUriToken::AnyUri(AnyUri(uri, _)) => Some(*uri),
+                unions::UnionAnyUriToken::Token(restrictions::EnumerationDefaultNamespaceTargetNamespaceLocal(Token(token))) => {
+                    match *token {
+                        "##targetNamespace" => self.target_namespace,
+                        "##local" => None,
+                        // Namespace declarations are not kept in the AST,
+                        // so the default namespace in scope is unknown.
+                        "##defaultNamespace" => panic!("##defaultNamespace is not supported in wildcards."),
+                        _ => unreachable!(),
+                    }
+                },
+            }
+        }).collect()
+    }
+
+    /// https://www.w3.org/TR/xmlschema11-1/#sec-src-wildcard
+    fn process_wildcard(&self,
+            namespace: &Option<xs::NamespaceList<'input>>,
+            not_namespace: &Option<restrictions::RestrictBasicNamespaceList<'input>>,
+            not_q_name: &Option<lists::UnionQNameTokenList<'input>>,
+            process_contents: &Option<restrictions::EnumerationSkipLaxStrict<'input>>,
+            ) -> Wildcard<'input> {
+        let namespaces = match (namespace, not_namespace) {
+            (None, None) => NamespaceConstraint::Any,
+            (Some(unions::UnionSpecialNamespaceListBasicNamespaceList::SpecialNamespaceList(special)), None) => {
+                match (special.0).0 {
+                    "##any" => NamespaceConstraint::Any,
+                    "##other" => {
+                        let mut namespaces = Vec::new();
+                        if self.target_namespace.is_some() {
+                            namespaces.push(self.target_namespace);
+                        }
+                        namespaces.push(None);
+                        NamespaceConstraint::Not(namespaces)
+                    },
+                    _ => unreachable!(),
+                }
+            },
+            (Some(unions::UnionSpecialNamespaceListBasicNamespaceList::BasicNamespaceList(list)), None) =>
+                NamespaceConstraint::Enumeration(self.process_namespace_list(list)),
+            (None, Some(not_namespace)) =>
+                NamespaceConstraint::Not(self.process_namespace_list(&not_namespace.0)),
+            (Some(_), Some(_)) =>
+                panic!("Wildcard has both namespace and notNamespace."),
+        };
+        let mut not_qnames = Vec::new();
+        let mut not_defined = false;
+        if let Some(not_q_name) = not_q_name {
+            for item in not_q_name.0.iter() {
+                match item {
+                    unions::UnionQNameToken::QName(qname) =>
+                        not_qnames.push(FullName::from_qname(qname, self.target_namespace)),
+                    unions::UnionQNameToken::Token(restrictions::Defined(Token("##defined"))) =>
+                        not_defined = true,
+                    // Generated wildcards are shared by all the content
+                    // models using them, so they cannot exclude the siblings
+                    // of each.
+                    unions::UnionQNameToken::Token(restrictions::Defined(Token("##definedSibling"))) =>
+                        panic!("notQName=\"##definedSibling\" is not supported."),
+                    unions::UnionQNameToken::Token(token) =>
+                        panic!("Unknown notQName token: {:?}", token),
+                }
+            }
+        }
+        let process_contents = match process_contents.as_ref().map(|x| (x.0).0) {
+            Some("skip") => ProcessContents::Skip,
+            Some("lax") => ProcessContents::Lax,
+            Some("strict") | None => ProcessContents::Strict,
+            _ => unreachable!(),
+        };
+        Wildcard { namespaces, not_qnames, not_defined, process_contents }
+    }
+
     fn process_sequence(&mut self,
             seq: &'ast xs::Sequence<'input>,
             inlinable: bool,
@@ -762,6 +1394,11 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         let substitution_group = &element.attr_substitution_group;
         let xs::Element { ref attrs, ref attr_id, ref attr_name, ref attr_type, ref attr_substitution_group, ref attr_default, ref attr_fixed, ref attr_nillable, ref attr_abstract, ref attr_final, ref attr_block, ref annotation, type_: ref child_type, ref alternative_alt_type, ref identity_constraint } = element;
         let annotation = annotation.iter().collect();
+        if attr_abstract.as_ref().map(|b| b.0).unwrap_or(false) {
+            self.abstract_elements.insert(name);
+        }
+        self.element_finals.insert(name, attr_final.as_ref().map(process_derivation_set).unwrap_or(self.final_default));
+        self.element_blocks.insert(name, attr_block.as_ref().map(process_block_set).unwrap_or(self.block_default));
         if let Some(heads) = attr_substitution_group {
             for head in &heads.0 {
                 let head = FullName::from_qname(head, self.target_namespace);
@@ -790,11 +1427,10 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                     )
             },
             (None, None) => {
-                RichType::new(
-                    NameHint::new("empty"),
-                    Type::Empty,
-                    self.process_annotation(&annotation),
-                    )
+                // https://www.w3.org/TR/xmlschema11-1/#declare-element
+                // (the type definition defaults to xs:anyType)
+                let doc = self.process_annotation(&annotation);
+                self.any_type(doc)
             },
             (Some(ref t1), Some(ref t2)) => panic!("Toplevel element '{:?}' has both a type attribute ({:?}) and a child type ({:?}).", name, t1, t2),
         };
@@ -802,6 +1438,18 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         self.elements.insert(name, type_);
     }
 
+    fn process_toplevel_attribute(&mut self, attribute: &'ast xs::Attribute<'input>) {
+        let xs::Attribute { ref attrs, ref attr_id, ref attr_type, ref attr_default, ref attr_fixed, ref attr_inheritable, ref attr_name, ref annotation, ref local_simple_type } = attribute;
+        let name = FullName::new(self.target_namespace, attr_name.0);
+        let type_ = match (attr_type, local_simple_type) {
+            (Some(t), None) => Some(SimpleType::Alias(FullName::from_qname(t, self.target_namespace))),
+            (None, Some(t)) => Some(self.process_local_simple_type(t).type_),
+            (None, None) => None,
+            (Some(ref t1), Some(ref t2)) => panic!("Toplevel attribute '{:?}' has both a type attribute ({:?}) and a child type ({:?}).", name, t1, t2),
+        };
+        self.attributes.insert(name, type_);
+    }
+
     fn process_local_element(&mut self,
             element: &'ast inline_elements::LocalElement<'input>,
             ) -> RichType<'input, Type<'input>> {
@@ -827,14 +1475,14 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
             let name = name.as_ref().expect("<element> has no name.").0;
 
             // https://www.w3.org/TR/xmlschema11-1/#dcl.elt.local
-            let qualified_form = match attr_form.as_ref().map(|x| ((x.0).0).0) {
+            let qualified_form = match attr_form.as_ref().map(|x| (x.0).0) {
                 Some("qualified") => true,
                 Some("unqualified") => false,
                 None => self.element_form_default_qualified,
                 _ => unreachable!(),
             };
             let namespace = match (attr_target_namespace, qualified_form) {
-                (Some(AnyUri(target_namespace)), _) => Some(*target_namespace),
+                (Some(AnyUri(target_namespace, _)), _) => Some(*target_namespace),
                 (None, true) => self.target_namespace,
                 (None, false) => None,
             };
@@ -889,10 +1537,19 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                         )
                 },
                 (None, None) => {
+                    // https://www.w3.org/TR/xmlschema11-1/#declare-element
+                    // (the type definition defaults to xs:anyType)
+                    let doc = self.process_annotation(&annotation);
+                    let mut t = self.any_type(doc);
+                    let struct_name = name_from_hint(&NameHint::new(name)).unwrap();
+                    let (elems, doc) = self.inline_elements.entry((namespace, name, t.attrs, t.type_))
+                            .or_insert((HashSet::new(), Documentation::new()));
+                    elems.insert(struct_name.clone());
+                    t.doc.extend(doc);
                     RichType::new(
-                        NameHint::new("empty"),
-                        Type::Empty,
-                        self.process_annotation(&annotation),
+                        NameHint::new(name),
+                        Type::Element(min_occurs, max_occurs, struct_name),
+                        t.doc,
                         )
                 },
                 (Some(ref t1), Some(ref t2)) => panic!("Element '{:?}' has both a type attribute ({:?}) and a child type ({:?}).", name, t1, t2),
@@ -905,9 +1562,21 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
         for attr_decl in &attr_decls.attribute {
             match attr_decl {
                 enums::AttrOrAttrGroup::Attribute(e) => {
-                    let name = e.attr_name.as_ref().map(|ncn| FullName::new(self.target_namespace, ncn.0));
+                    // https://www.w3.org/TR/xmlschema11-1/#dcl.attr.local
+                    let qualified_form = match e.attr_form.as_ref().map(|x| (x.0).0) {
+                        Some("qualified") => true,
+                        Some("unqualified") => false,
+                        None => self.attribute_form_default_qualified,
+                        _ => unreachable!(),
+                    };
+                    let namespace = match (&e.attr_target_namespace, qualified_form) {
+                        (Some(AnyUri(target_namespace, _)), _) => Some(*target_namespace),
+                        (None, true) => self.target_namespace,
+                        (None, false) => None,
+                    };
+                    let name = e.attr_name.as_ref().map(|ncn| FullName::new(namespace, ncn.0));
                     let mut type_attr: Option<QName<'input>> = e.attr_type;
-                    let use_ = match e.attr_use.as_ref().map(|x| ((x.0).0).0) {
+                    let use_ = match e.attr_use.as_ref().map(|x| (x.0).0) {
                         Some("prohibited") => AttrUse::Prohibited,
                         Some("required") => AttrUse::Required,
                         Some("optional") => AttrUse::Optional,
@@ -933,7 +1602,10 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                             panic!("<attribute> has a type but no name."),
                         (_, Some(_), Some(_), None) | (_, Some(_), None, Some(_)) =>
                             panic!("<attribute> has a type and a ref."),
-                        (_, Some(ref_), None, None) => (), // TODO
+                        (_, Some(ref_), None, None) => {
+                            let ref_ = FullName::from_qname(&ref_, self.target_namespace);
+                            attrs.refs.push((None, use_, ref_));
+                        },
                     }
                 },
                 enums::AttrOrAttrGroup::AttributeGroup(e) => {
@@ -941,8 +1613,10 @@ impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
                 },
             }
         }
-        if attr_decls.any_attribute.is_some() {
-            attrs.any_attributes = true;
+        if let Some(ref any_attribute) = attr_decls.any_attribute {
+            let xs::AnyAttribute { attrs: ref attrs2, ref attr_id, ref attr_not_q_name, ref attr_namespace, ref attr_not_namespace, ref attr_process_contents, ref annotation } = *any_attribute;
+            let wildcard = self.process_wildcard(attr_namespace, attr_not_namespace, attr_not_q_name, attr_process_contents);
+            attrs.any_attributes = Some(wildcard);
         }
         attrs
     }
@@ -0,0 +1,509 @@
+//! Checks types derived by restriction only allow a subset of what their
+//! base allows.
+//!
+//! https://www.w3.org/TR/xmlschema11-1/#derivation-ok-restriction
+//! https://www.w3.org/TR/xmlschema11-2/#rf-facets
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use names::FullName;
+use processor::*;
+use support::Facets;
+use upa::{Automaton, Fragment, Term};
+
+/// A state of a content model automaton: either the start, or the last
+/// particle matched.
+type State = Option<usize>;
+
+fn next_states(automaton: &Automaton, fragment: &Fragment, state: State) -> Vec<usize> {
+    match state {
+        None => fragment.first.iter().cloned().collect(),
+        Some(position) => automaton.follow[position].iter().cloned().collect(),
+    }
+}
+
+fn is_final(fragment: &Fragment, state: State) -> bool {
+    match state {
+        None => fragment.nullable,
+        Some(position) => fragment.last.contains(&position),
+    }
+}
+
+/// Returns the particles of the base matching this name. Element particles
+/// take precedence over wildcards.
+fn match_name<'input>(base: &Automaton<'input>, positions: &[usize], name: &FullName<'input>) -> BTreeSet<usize> {
+    let elements: BTreeSet<_> = positions.iter().cloned().filter(|&position| match base.terms[position] {
+        Term::Element(_, ref names, _) => names.contains(name),
+        Term::Wildcard(_) => false,
+    }).collect();
+    if !elements.is_empty() {
+        return elements;
+    }
+    positions.iter().cloned().filter(|&position| match base.terms[position] {
+        Term::Element(_, _, _) => false,
+        Term::Wildcard(ref wildcard) => wildcard.allows_namespace(name.namespace()),
+    }).collect()
+}
+
+/// Bases of the built-in types derived from other ones than
+/// xs:anySimpleType.
+///
+/// https://www.w3.org/TR/xmlschema11-2/#built-in-datatypes
+const BUILT_IN_BASES: &'static [(&'static str, &'static str)] = &[
+    ("normalizedString", "string"),
+    ("token", "normalizedString"),
+    ("language", "token"),
+    ("NMTOKEN", "token"),
+    ("Name", "token"),
+    ("NCName", "Name"),
+    ("ID", "NCName"),
+    ("IDREF", "NCName"),
+    ("ENTITY", "NCName"),
+    ("integer", "decimal"),
+    ("nonPositiveInteger", "integer"),
+    ("negativeInteger", "nonPositiveInteger"),
+    ("long", "integer"),
+    ("int", "long"),
+    ("short", "int"),
+    ("byte", "short"),
+    ("nonNegativeInteger", "integer"),
+    ("unsignedLong", "nonNegativeInteger"),
+    ("unsignedInt", "unsignedLong"),
+    ("unsignedShort", "unsignedInt"),
+    ("unsignedByte", "unsignedShort"),
+    ("positiveInteger", "nonNegativeInteger"),
+    ("yearMonthDuration", "duration"),
+    ("dayTimeDuration", "duration"),
+    ("dateTimeStamp", "dateTime"),
+];
+
+fn any_type<'input>() -> FullName<'input> {
+    FullName::new(Some(SCHEMA_URI), "anyType")
+}
+
+fn any_simple_type<'input>() -> FullName<'input> {
+    FullName::new(Some(SCHEMA_URI), "anySimpleType")
+}
+
+/// Returns the name of a type, if it is not anonymous.
+fn type_name<'input>(type_: &Type<'input>) -> Option<FullName<'input>> {
+    match type_ {
+        Type::Alias(name) | Type::Simple(SimpleType::Alias(name)) => Some(*name),
+        _ => None,
+    }
+}
+
+/// Returns the base of an anonymous type.
+fn anonymous_base<'input>(type_: &Type<'input>) -> FullName<'input> {
+    match type_ {
+        Type::Restriction(base, _) | Type::Extension(base, _) |
+        Type::Simple(SimpleType::Restriction(base, _)) => *base,
+        Type::Simple(SimpleType::Alias(name)) | Type::Alias(name) => *name,
+        Type::Simple(_) => any_simple_type(),
+        Type::OpenContent(_, inner) | Type::Mixed(inner) => anonymous_base(&inner.type_),
+        // Complex types which are not derived restrict xs:anyType.
+        _ => any_type(),
+    }
+}
+
+/// Orders whiteSpace values from the least to the most normalizing.
+fn white_space_rank(white_space: Option<&str>) -> usize {
+    match white_space {
+        None | Some("preserve") => 0,
+        Some("replace") => 1,
+        _ => 2,
+    }
+}
+
+/// Checks the facets of a restriction step do not widen the ones of its
+/// base, nor change the ones the base declared fixed.
+///
+/// Enumerations are compared lexically, as value spaces are only known at
+/// parse time.
+fn check_facets(facets: &Facets, base: &Facets, fixed: &HashSet<&'static str>) -> Result<(), String> {
+    fn check<T: PartialOrd>(name: &str, value: &Option<T>, base_name: &str, base_value: &Option<T>, ok: fn(&T, &T) -> bool) -> Result<(), String> {
+        match (value, base_value) {
+            (Some(value), Some(base_value)) if !ok(value, base_value) =>
+                Err(format!("{} is wider than the base's {}", name, base_name)),
+            _ => Ok(()),
+        }
+    }
+    check("minExclusive", &facets.min_exclusive, "minExclusive", &base.min_exclusive, |a, b| a >= b)?;
+    check("minExclusive", &facets.min_exclusive, "minInclusive", &base.min_inclusive, |a, b| a >= b)?;
+    check("minInclusive", &facets.min_inclusive, "minInclusive", &base.min_inclusive, |a, b| a >= b)?;
+    check("minInclusive", &facets.min_inclusive, "minExclusive", &base.min_exclusive, |a, b| a > b)?;
+    check("maxExclusive", &facets.max_exclusive, "maxExclusive", &base.max_exclusive, |a, b| a <= b)?;
+    check("maxExclusive", &facets.max_exclusive, "maxInclusive", &base.max_inclusive, |a, b| a <= b)?;
+    check("maxInclusive", &facets.max_inclusive, "maxInclusive", &base.max_inclusive, |a, b| a <= b)?;
+    check("maxInclusive", &facets.max_inclusive, "maxExclusive", &base.max_exclusive, |a, b| a < b)?;
+    check("totalDigits", &facets.total_digits, "totalDigits", &base.total_digits, |a, b| a <= b)?;
+    check("fractionDigits", &facets.fraction_digits, "fractionDigits", &base.fraction_digits, |a, b| a <= b)?;
+    check("length", &facets.length, "length", &base.length, |a, b| a == b)?;
+    check("length", &facets.length, "minLength", &base.min_length, |a, b| a >= b)?;
+    check("length", &facets.length, "maxLength", &base.max_length, |a, b| a <= b)?;
+    check("minLength", &facets.min_length, "minLength", &base.min_length, |a, b| a >= b)?;
+    check("maxLength", &facets.max_length, "maxLength", &base.max_length, |a, b| a <= b)?;
+    if let (Some(values), Some(base_values)) = (&facets.enumeration, &base.enumeration) {
+        if let Some(value) = values.iter().find(|value| !base_values.contains(value)) {
+            return Err(format!("enumeration value {:?} is not allowed by the base", value));
+        }
+    }
+    if facets.white_space.is_some() && white_space_rank(facets.white_space) < white_space_rank(base.white_space) {
+        return Err(format!("whiteSpace {:?} is wider than the base's {:?}", facets.white_space.unwrap(), base.white_space.unwrap()));
+    }
+
+    for &name in fixed {
+        let changed = match name {
+            "minExclusive" => facets.min_exclusive.is_some() && facets.min_exclusive != base.min_exclusive,
+            "minInclusive" => facets.min_inclusive.is_some() && facets.min_inclusive != base.min_inclusive,
+            "maxExclusive" => facets.max_exclusive.is_some() && facets.max_exclusive != base.max_exclusive,
+            "maxInclusive" => facets.max_inclusive.is_some() && facets.max_inclusive != base.max_inclusive,
+            "totalDigits" => facets.total_digits.is_some() && facets.total_digits != base.total_digits,
+            "fractionDigits" => facets.fraction_digits.is_some() && facets.fraction_digits != base.fraction_digits,
+            "length" => facets.length.is_some() && facets.length != base.length,
+            "minLength" => facets.min_length.is_some() && facets.min_length != base.min_length,
+            "maxLength" => facets.max_length.is_some() && facets.max_length != base.max_length,
+            "whiteSpace" => facets.white_space.is_some() && facets.white_space != base.white_space,
+            "explicitTimezone" => facets.explicit_timezone.is_some() && facets.explicit_timezone != base.explicit_timezone,
+            _ => false,
+        };
+        if changed {
+            return Err(format!("{} is fixed by the base", name));
+        }
+    }
+    Ok(())
+}
+
+impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
+    /// Checks every sequence of elements accepted by the derived content
+    /// model is accepted by the base one, and every element keeps the type
+    /// of the base's or one derived from it, by walking both automata side
+    /// by side.
+    ///
+    /// Both automata unroll counted repetitions, so occurrence ranges must
+    /// be narrowed too. As unrolled automata are not deterministic, the
+    /// base is walked in all the states it may be in.
+    fn check_content_subset(&self, derived: &Automaton<'input>, derived_fragment: &Fragment, base: &Automaton<'input>, base_fragment: &Fragment) -> Result<(), String> {
+        if derived.approximated || base.approximated {
+            return Err("its counted repetitions are too large to be checked".to_string());
+        }
+        let mut seen = HashSet::new();
+        let mut todo: Vec<(State, BTreeSet<State>)> = vec![(None, Some(None).into_iter().collect())];
+        while let Some((derived_state, base_states)) = todo.pop() {
+            if !seen.insert((derived_state, base_states.clone())) {
+                continue;
+            }
+            if is_final(derived_fragment, derived_state) && !base_states.iter().any(|&base_state| is_final(base_fragment, base_state)) {
+                return Err("the content may end before the base's does".to_string());
+            }
+            let base_positions: BTreeSet<_> = base_states.iter()
+                .flat_map(|&base_state| next_states(base, base_fragment, base_state))
+                .collect();
+            let base_positions: Vec<_> = base_positions.into_iter().collect();
+            for derived_position in next_states(derived, derived_fragment, derived_state) {
+                match derived.terms[derived_position] {
+                    Term::Element(ref derived_name, ref names, ref declaration) => {
+                        for name in names {
+                            let matched = match_name(base, &base_positions, name);
+                            if matched.is_empty() {
+                                return Err(format!("element {} is not allowed by the base at this point", name));
+                            }
+                            for &base_position in matched.iter() {
+                                if let Term::Element(ref base_name, _, ref base_declaration) = base.terms[base_position] {
+                                    // Members of substitution groups were
+                                    // checked against their head.
+                                    if name != derived_name || name != base_name {
+                                        continue;
+                                    }
+                                    if let (Some(declaration), Some(base_declaration)) = (declaration, base_declaration) {
+                                        if !self.is_derived_declaration(declaration, base_declaration) {
+                                            return Err(format!("the type of element {} is not derived from the base's", name));
+                                        }
+                                    }
+                                }
+                            }
+                            todo.push((Some(derived_position), matched.into_iter().map(Some).collect()));
+                        }
+                    },
+                    Term::Wildcard(ref wildcard) => {
+                        let matched: BTreeSet<_> = base_positions.iter().cloned().filter(|&position| match base.terms[position] {
+                            Term::Element(_, _, _) => false,
+                            Term::Wildcard(ref base_wildcard) => wildcard.is_subset(base_wildcard),
+                        }).collect();
+                        if matched.is_empty() {
+                            return Err(format!("wildcard {:?} is not allowed by the base at this point", wildcard.namespaces));
+                        }
+                        for &base_position in matched.iter() {
+                            if let Term::Wildcard(ref base_wildcard) = base.terms[base_position] {
+                                if wildcard.process_contents < base_wildcard.process_contents {
+                                    return Err(format!("wildcard {:?} processes its content less strictly than the base's", wildcard.namespaces));
+                                }
+                            }
+                        }
+                        todo.push((Some(derived_position), matched.into_iter().map(Some).collect()));
+                    },
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether an element declaration may restrict one of the base: its
+    /// type must be the base's, or derived from it.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-1/#cos-content-act-restrict
+    fn is_derived_declaration(&self, declaration: &(Attrs<'input>, Type<'input>), base: &(Attrs<'input>, Type<'input>)) -> bool {
+        declaration == base || self.is_any_type(base) || self.is_derived_type(&declaration.1, &base.1)
+    }
+
+    /// Whether a type is the base type or derived from it.
+    ///
+    /// Derivations cannot be followed through types declared in other
+    /// schemas, so types derived from them are not known to derive from
+    /// anything else.
+    fn is_derived_type(&self, type_: &Type<'input>, base: &Type<'input>) -> bool {
+        if type_ == base {
+            return true;
+        }
+        let base = match type_name(base) {
+            Some(base) => base,
+            // Anonymous types are only derived from themselves.
+            None => return false,
+        };
+        let mut current = type_name(type_).unwrap_or_else(|| anonymous_base(type_));
+        let mut seen = HashSet::new();
+        loop {
+            if current == base {
+                return true;
+            }
+            if !seen.insert(current) {
+                return false; // Circular derivations are refused by check_derivations
+            }
+            current = match self.base_type_name(&current) {
+                Some(base) => base,
+                None => return false, // xs:anyType, or declared in an other schema
+            };
+        }
+    }
+
+    /// Same as `is_derived_type`, for the types of attribute declarations,
+    /// which are xs:anySimpleType when not given.
+    fn is_derived_simple_type(&self, type_: &Option<SimpleType<'input>>, base: &Option<SimpleType<'input>>) -> bool {
+        let any_simple_type = SimpleType::Alias(any_simple_type());
+        let type_ = Type::Simple(type_.clone().unwrap_or_else(|| any_simple_type.clone()));
+        let base = Type::Simple(base.clone().unwrap_or(any_simple_type));
+        self.is_derived_type(&type_, &base)
+    }
+
+    /// Whether this is the anonymous type of an untyped local element,
+    /// ie. the content of xs:anyType.
+    fn is_any_type(&self, declaration: &(Attrs<'input>, Type<'input>)) -> bool {
+        let (ref attrs, ref type_) = *declaration;
+        let wildcard = match attrs.any_attributes {
+            Some(ref wildcard) if attrs.named.is_empty() && attrs.refs.is_empty() && attrs.group_refs.is_empty() => wildcard,
+            _ => return false,
+        };
+        let any_wildcard = wildcard.namespaces == NamespaceConstraint::Any &&
+            wildcard.not_qnames.is_empty() && !wildcard.not_defined;
+        let inner = match type_ {
+            Type::Mixed(inner) if any_wildcard => inner,
+            _ => return false,
+        };
+        match inner.type_ {
+            Type::Sequence(0, max_occurs, ref struct_name) if max_occurs == usize::max_value() => {
+                self.sequences.iter()
+                    .find(|&(_, (struct_names, _))| struct_names.contains(struct_name))
+                    .map_or(false, |(items, _)| items.len() == 1 && items[0].type_ == Type::Any(wildcard.clone()))
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the base of a named type, or None for xs:anyType and the
+    /// types declared in an other schema.
+    fn base_type_name(&self, name: &FullName<'input>) -> Option<FullName<'input>> {
+        let derivation = self.derivations.iter().find(|&&(ref derived, _, method)| {
+            *derived == Some(*name) && (method == DerivationMethod::Extension || method == DerivationMethod::Restriction)
+        });
+        if let Some(&(_, base, _)) = derivation {
+            return Some(base);
+        }
+        if name.namespace() == Some(SCHEMA_URI) {
+            return match name.local_name() {
+                "anyType" => None,
+                "anySimpleType" => Some(any_type()),
+                local_name => match BUILT_IN_BASES.iter().find(|&&(type_, _)| type_ == local_name) {
+                    Some(&(_, base)) => Some(FullName::new(Some(SCHEMA_URI), base)),
+                    None => Some(any_simple_type()),
+                },
+            };
+        }
+        if self.simple_types.contains_key(name) {
+            Some(any_simple_type()) // Lists and unions
+        }
+        else if self.types.contains_key(name) {
+            Some(any_type())
+        }
+        else {
+            None
+        }
+    }
+
+    /// Panics if a complex type derived by restriction allows content or
+    /// attributes its base does not.
+    pub fn check_restrictions(&self) {
+        let mut restrictions = Vec::new();
+        for (name, type_) in self.types.iter() {
+            restrictions.push((format!("type {}", name), &type_.type_, &type_.attrs));
+        }
+        for (name, element) in self.elements.iter() {
+            restrictions.push((format!("element {}", name), &element.type_, &element.attrs));
+        }
+        for &(namespace, local_name, ref attrs, ref type_) in self.inline_elements.keys() {
+            restrictions.push((format!("element {}", FullName::new(namespace, local_name)), type_, attrs));
+        }
+        for (description, type_, attrs) in restrictions {
+            let mut type_ = type_;
+            while let Type::OpenContent(_, inner) | Type::Mixed(inner) = type_ {
+                type_ = &inner.type_;
+            }
+            if let Type::Restriction(base, inner) = type_ {
+                let base_type = match self.types.get(base) {
+                    Some(base_type) => base_type,
+                    None => continue, // xs:anyType, or declared in an other schema
+                };
+                if let Err(e) = self.check_restriction(base_type, &inner.type_, attrs) {
+                    panic!("{} is not a valid restriction of {}: {}", description, base, e);
+                }
+            }
+        }
+    }
+
+    /// Panics if a simple type derived by restriction has facets that widen
+    /// the ones of its base.
+    pub fn check_simple_restrictions(&self) {
+        for &(ref base, ref facets) in self.simple_restrictions.iter() {
+            let (base_facets, fixed) = self.inherited_facets(base);
+            if let Err(e) = check_facets(facets, &base_facets, &fixed) {
+                panic!("restriction of {} is not valid: {}", base, e);
+            }
+        }
+    }
+
+    /// Returns the facets a simple type applies to its values, and the
+    /// names of the ones declared fixed along its derivation chain.
+    fn inherited_facets(&self, name: &FullName<'input>) -> (Facets<'input>, HashSet<&'static str>) {
+        match self.simple_types.get(name).map(|&(ref type_, _)| &type_.type_) {
+            Some(SimpleType::Alias(target)) => self.inherited_facets(target),
+            Some(SimpleType::Restriction(base, facets)) => {
+                let (base_facets, mut fixed) = self.inherited_facets(base);
+                if let Some(own_fixed) = self.fixed_facets.get(&(*base, facets.clone())) {
+                    fixed.extend(own_fixed);
+                }
+                (facets.restrict(&base_facets), fixed)
+            },
+            // Built-in types, lists, unions, or declared in an other schema
+            _ => (Facets::default(), HashSet::new()),
+        }
+    }
+
+    fn check_restriction(&self, base: &RichType<'input, Type<'input>>, type_: &Type<'input>, attrs: &Attrs<'input>) -> Result<(), String> {
+        let mut derived_automaton = Automaton::exact();
+        let derived_fragment = self.build_automaton(&mut derived_automaton, type_);
+        let mut base_automaton = Automaton::exact();
+        let base_fragment = self.build_automaton(&mut base_automaton, &base.type_);
+        self.check_content_subset(&derived_automaton, &derived_fragment, &base_automaton, &base_fragment)?;
+
+        // https://www.w3.org/TR/xmlschema11-1/#derivation-ok-restriction
+        // clauses 2 to 4
+        let mut base_uses = HashMap::new();
+        let mut base_wildcard = None;
+        self.attribute_uses(base, &mut base_uses, &mut base_wildcard);
+        let mut uses = HashMap::new();
+        let mut wildcard = None;
+        self.add_attribute_uses(attrs, &mut uses, &mut wildcard);
+        for (name, &(ref use_, ref type_)) in uses.iter() {
+            if let (Some(&(_, ref base_type)), false) = (base_uses.get(name), *use_ == AttrUse::Prohibited) {
+                if !self.is_derived_simple_type(type_, base_type) {
+                    return Err(format!("the type of attribute {} is not derived from the base's", name));
+                }
+            }
+            match (base_uses.get(name).map(|&(ref base_use, _)| base_use), use_) {
+                (Some(AttrUse::Required), AttrUse::Required) => (),
+                (Some(AttrUse::Required), _) =>
+                    return Err(format!("attribute {} is required by the base", name)),
+                (Some(_), _) | (None, AttrUse::Prohibited) => (),
+                (None, _) => {
+                    let allowed = base_wildcard.as_ref().map(|w: &Wildcard| w.allows_namespace(name.namespace())).unwrap_or(false);
+                    if !allowed {
+                        return Err(format!("attribute {} is not allowed by the base", name));
+                    }
+                },
+            }
+        }
+        match (&wildcard, &base_wildcard) {
+            (None, _) => (),
+            (Some(w), Some(base_w)) if w.is_subset(base_w) => {
+                if w.process_contents < base_w.process_contents {
+                    return Err(format!("attribute wildcard {:?} processes its content less strictly than the base's", w.namespaces));
+                }
+            },
+            (Some(w), _) => return Err(format!("attribute wildcard {:?} is not allowed by the base", w.namespaces)),
+        }
+        Ok(())
+    }
+
+    /// Collects the attribute uses (with the types of their declarations)
+    /// and attribute wildcard of a complex type, including the ones it
+    /// inherits.
+    fn attribute_uses(&self, type_: &RichType<'input, Type<'input>>, uses: &mut HashMap<FullName<'input>, (AttrUse, Option<SimpleType<'input>>)>, wildcard: &mut Option<Wildcard<'input>>) {
+        match type_.type_ {
+            Type::Alias(ref name) => {
+                if let Some(target) = self.types.get(name) {
+                    self.attribute_uses(target, uses, wildcard);
+                }
+            },
+            Type::Extension(ref base, ref ext_type) => {
+                if let Some(base) = self.types.get(base) {
+                    self.attribute_uses(base, uses, wildcard);
+                }
+                self.attribute_uses(ext_type, uses, wildcard);
+            },
+            Type::Restriction(ref base, ref inner) => {
+                if let Some(base) = self.types.get(base) {
+                    self.attribute_uses(base, uses, wildcard);
+                }
+                // The wildcard of a restriction is only its own.
+                *wildcard = None;
+                self.attribute_uses(inner, uses, wildcard);
+            },
+            Type::OpenContent(_, ref inner) |
+            Type::Mixed(ref inner) => self.attribute_uses(inner, uses, wildcard),
+            _ => (),
+        }
+        self.add_attribute_uses(&type_.attrs, uses, wildcard);
+    }
+
+    fn add_attribute_uses(&self, attrs: &Attrs<'input>, uses: &mut HashMap<FullName<'input>, (AttrUse, Option<SimpleType<'input>>)>, wildcard: &mut Option<Wildcard<'input>>) {
+        for &(name, use_, ref type_) in attrs.named.iter() {
+            uses.insert(name, (use_, type_.clone()));
+        }
+        for &(_, use_, ref_) in attrs.refs.iter() {
+            // Attributes declared in an other schema are taken as
+            // xs:anySimpleType.
+            let type_ = self.attributes.get(&ref_).cloned().unwrap_or(None);
+            uses.insert(ref_, (use_, type_));
+        }
+        for group_name in attrs.group_refs.iter() {
+            if let Some(group_attrs) = self.attribute_groups.get(group_name) {
+                self.add_attribute_uses(group_attrs, uses, wildcard);
+            }
+        }
+        if let Some(ref own_wildcard) = attrs.any_attributes {
+            *wildcard = Some(match wildcard.take() {
+                Some(w) => w.union(own_wildcard),
+                None => own_wildcard.clone(),
+            });
+        }
+    }
+}
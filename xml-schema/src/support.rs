@@ -1,13 +1,22 @@
 use std::marker::PhantomData;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 pub use std::str::FromStr;
 
 pub use xmlparser::{Token as XmlToken, Tokenizer, ElementEnd};
+use xmlparser::EntityDefinition;
 
 pub use primitives::*; // TODO: remove the pub?
 pub use names::FullName;
 
 pub use bigfloat::BigFloatNotNaN;
+pub use uri::UriReference;
+use uri;
+use patterns;
+use xml_utils::{is_whitespace, decode_text, decode_entity_value, TextKind, MAX_ENTITY_EXPANSION};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct Facets<'input> {
@@ -22,35 +31,171 @@ pub struct Facets<'input> {
     pub max_length: Option<usize>,
     pub enumeration: Option<Vec<&'input str>>,
     pub white_space: Option<&'input str>,
-    pub pattern: Option<&'input str>,
+    /// Patterns of the same derivation step; values must match one of them.
+    pub pattern: Option<Vec<&'input str>>,
+    /// Patterns of the base types, one list per derivation step, which
+    /// values must match too.
+    pub inherited_patterns: Vec<Vec<&'input str>>,
     pub assertion: Option<&'input str>,
     pub explicit_timezone: Option<&'input str>,
 }
 
+fn narrowest<T: Ord>(a: Option<T>, b: Option<T>, narrower: fn(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(narrower(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl<'input> Facets<'input> {
+    /// Combines the facets of a derived type with the ones of its base.
+    ///
+    /// Bounds take the narrowest value, and patterns of all steps are kept.
+    /// Other facets of the derived type override the base's, as the
+    /// processor checks they do not widen them.
+    pub fn restrict(&self, base: &Facets<'input>) -> Facets<'input> {
+        let mut inherited_patterns = self.inherited_patterns.clone();
+        inherited_patterns.extend(base.pattern.clone());
+        inherited_patterns.extend(base.inherited_patterns.iter().cloned());
+        Facets {
+            min_exclusive: narrowest(self.min_exclusive.clone(), base.min_exclusive.clone(), max),
+            min_inclusive: narrowest(self.min_inclusive.clone(), base.min_inclusive.clone(), max),
+            max_exclusive: narrowest(self.max_exclusive.clone(), base.max_exclusive.clone(), min),
+            max_inclusive: narrowest(self.max_inclusive.clone(), base.max_inclusive.clone(), min),
+            total_digits: narrowest(self.total_digits, base.total_digits, min),
+            fraction_digits: narrowest(self.fraction_digits, base.fraction_digits, min),
+            length: self.length.or(base.length),
+            min_length: narrowest(self.min_length, base.min_length, max),
+            max_length: narrowest(self.max_length, base.max_length, min),
+            enumeration: self.enumeration.clone().or(base.enumeration.clone()),
+            white_space: self.white_space.or(base.white_space),
+            pattern: self.pattern.clone(),
+            inherited_patterns,
+            assertion: self.assertion.or(base.assertion),
+            explicit_timezone: self.explicit_timezone.or(base.explicit_timezone),
+        }
+    }
+
+    /// Whether a value matches the patterns of all derivation steps.
+    ///
+    /// https://www.w3.org/TR/xmlschema11-2/#src-multiple-patterns
+    pub fn matches_patterns(&self, value: &str) -> bool {
+        self.pattern.iter().chain(self.inherited_patterns.iter()).all(|step| {
+            step.iter().any(|pattern| patterns::is_match(pattern, value))
+        })
+    }
+}
+
 #[derive(Debug,PartialEq)]
 pub struct List<'input, Item>(Vec<Item>, PhantomData<&'input ()>);
 
+/// Stores character data which is not written as such in the document:
+/// values with references, CDATA sections or line ends to decode.
+///
+/// Parsed values borrow it like the document itself, so it is never
+/// freed. Only values which differ from what is written are stored, and
+/// `InnerStream::decode` stores each of them once.
+pub(crate) fn store_decoded<'input>(text: String) -> &'input str {
+    Box::leak(text.into_boxed_str())
+}
+
 pub type Stream<'input> = Box<InnerStream<'input>>;
 pub struct InnerStream<'input> {
     pub(crate) index: usize,
+    /// Number of elements opened and not closed yet.
+    depth: usize,
     passed_prelude: bool,
     tokens: Vec<XmlToken<'input>>,
+    contents: Vec<ContentFrame>,
+    /// Replacement texts of the internal general entities declared in the
+    /// document type declaration.
+    entities: HashMap<&'input str, &'input str>,
+    /// How many more bytes expanding entity references may produce.
+    expansion_budget: Cell<usize>,
+    /// Values decoded so far, by position and kind, so backtracking does
+    /// not expand their entity references again.
+    decoded_values: RefCell<HashMap<(usize, usize, TextKind), &'input str>>,
+}
+
+/// Content of an element being parsed.
+struct ContentFrame {
+    /// Depth of the element's children.
+    depth: usize,
+    /// Index of the first token of the content.
+    start: usize,
+    /// Whether character data may appear between the children.
+    mixed: bool,
+    /// Interleaved open content of the element, if any.
+    open_content: Option<OpenContent>,
+    /// Token ranges of the wildcard elements skipped so far.
+    captured: Vec<(usize, usize)>,
 }
 
 impl<'input> InnerStream<'input> {
     pub fn new(tokenizer: Tokenizer<'input>) -> InnerStream<'input> {
-        InnerStream { index: 0, passed_prelude: false, tokens: tokenizer.into_iter().map(|o| o.unwrap()).collect() }
+        InnerStream { index: 0, depth: 0, passed_prelude: false, tokens: tokenizer.into_iter().map(|o| o.unwrap()).collect(), contents: Vec::new(), entities: HashMap::new(), expansion_budget: Cell::new(MAX_ENTITY_EXPANSION), decoded_values: RefCell::new(HashMap::new()) }
     }
 
     #[inline]
     pub fn transaction(&self) -> Transaction {
-        Transaction { initial_index: self.index }
+        Transaction { initial_index: self.index, initial_depth: self.depth }
+    }
+
+    /// Called when starting to parse the content of an element, with the
+    /// element's interleaved open content, if any, and whether its content
+    /// is mixed.
+    pub fn start_content(&mut self, open_content: Option<OpenContent>, mixed: bool) {
+        let depth = self.depth;
+        // Frames at this depth or below belong to elements which are not
+        // being parsed anymore.
+        self.contents.retain(|frame| frame.depth < depth);
+        self.contents.push(ContentFrame { depth, start: self.index, mixed, open_content, captured: Vec::new() });
+    }
+
+    /// Decodes character data of the document, with the entities it
+    /// declares; see `xml_utils::decode_text`. Values which have nothing
+    /// to decode are borrowed from the document.
+    pub fn decode(&self, raw: &'input str, kind: TextKind) -> Option<&'input str> {
+        let key = (raw.as_ptr() as usize, raw.len(), kind);
+        if let Some(text) = self.decoded_values.borrow().get(&key) {
+            return Some(*text);
+        }
+        let mut budget = self.expansion_budget.get();
+        let result = decode_text(raw, kind, &self.entities, &mut budget);
+        self.expansion_budget.set(budget);
+        let text = match result? {
+            Cow::Borrowed(text) => text,
+            Cow::Owned(text) => store_decoded(text),
+        };
+        self.decoded_values.borrow_mut().insert(key, text);
+        Some(text)
+    }
+
+    /// Position of the element whose start tag was just read.
+    pub fn element_start(&self) -> ElementPosition {
+        ElementPosition { index: self.index - 1, depth: self.depth - 1 }
+    }
+
+    fn current_content(&mut self) -> Option<&mut ContentFrame> {
+        let depth = self.depth;
+        self.contents.retain(|frame| frame.depth <= depth);
+        self.contents.last_mut().filter(|frame| frame.depth == depth)
+    }
+
+    /// Whether character data may appear at the current position, ie. outside
+    /// any element or in the content of an element whose content is mixed.
+    pub fn allows_text(&self) -> bool {
+        match self.contents.iter().rev().find(|frame| frame.depth <= self.depth) {
+            Some(frame) if frame.depth == self.depth => frame.mixed,
+            _ => true,
+        }
     }
 }
 
 #[must_use]
 pub struct Transaction {
     initial_index: usize,
+    initial_depth: usize,
 }
 
 impl Transaction {
@@ -61,7 +206,17 @@ impl Transaction {
     #[inline]
     pub fn rollback(self, stream: &mut InnerStream) {
         //println!("// Rolling back {} tokens", stream.index - self.initial_index);
-        stream.index = self.initial_index
+        stream.index = self.initial_index;
+        stream.depth = self.initial_depth;
+    }
+
+    /// Same as `rollback`, after something was parsed from the tokens
+    /// given back: the parse context forgets what was reported about
+    /// them, as they will be parsed again.
+    #[inline]
+    pub fn rollback_with<'input, TParseContext: ParseContext<'input>>(self, stream: &mut InnerStream, parse_context: &mut TParseContext) {
+        parse_context.on_rollback(self.initial_index);
+        self.rollback(stream);
     }
 }
 
@@ -70,25 +225,45 @@ impl<'input> Iterator for InnerStream<'input> {
     fn next(&mut self) -> Option<Self::Item> {
         if !self.passed_prelude {
             self.passed_prelude = true;
+            let mut entities = HashMap::new();
             loop {
                 let tok = self.next().unwrap();
                 match tok {
-                    XmlToken::EntityDeclaration(_, _) |
+                    XmlToken::EntityDeclaration(name, EntityDefinition::EntityValue(value)) => {
+                        let replacement_text = match decode_entity_value(value.to_str()) {
+                            Some(Cow::Borrowed(text)) => text,
+                            Some(Cow::Owned(text)) => store_decoded(text),
+                            // The malformed character reference is kept, so
+                            // decoding a reference to the entity fails.
+                            None => value.to_str(),
+                        };
+                        // The first declaration of an entity is binding.
+                        entities.entry(name.to_str()).or_insert(replacement_text);
+                    },
+                    XmlToken::EntityDeclaration(_, _) | // External entities are not read.
                     XmlToken::Declaration(_, _, _) |
                     XmlToken::DtdStart(_, _) |
                     XmlToken::Comment(_) => (),
                     XmlToken::DtdEnd => break,
                     _ => {
+                        self.entities = entities;
                         return Some(tok);
                     }
                 }
             }
+            self.entities = entities;
         }
         let tok = self.tokens.get(self.index);
         //println!("// Reading {:?}", tok);
         match tok {
             Some(res) => {
                 self.index += 1;
+                match res {
+                    XmlToken::ElementStart(_, _) => self.depth += 1,
+                    XmlToken::ElementEnd(ElementEnd::Empty) |
+                    XmlToken::ElementEnd(ElementEnd::Close(_, _)) => self.depth -= 1,
+                    _ => (),
+                }
                 Some(res.clone())
             }
             None => None
@@ -97,28 +272,257 @@ impl<'input> Iterator for InnerStream<'input> {
 }
 
 
+/// Where an element starts in a stream, so it can be parsed again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElementPosition {
+    index: usize,
+    depth: usize,
+}
+
+/// The namespace bound to the `xml` prefix.
+pub const XML_URI: &'static str = "http://www.w3.org/XML/1998/namespace";
+/// The namespace of namespace declarations, bound to no prefix.
+pub const XMLNS_URI: &'static str = "http://www.w3.org/2000/xmlns/";
+/// The namespace of xsi:type and the other instance attributes.
+pub const XSI_URI: &'static str = "http://www.w3.org/2001/XMLSchema-instance";
+
 #[derive(Clone)]
 pub struct ParentContext<'input> {
     pub namespaces: HashMap<&'input str, &'input str>,
+    /// The element being parsed, if any.
+    pub element: Option<ElementPosition>,
+    /// The base URI in scope, from the document's URI and `xml:base`
+    /// attributes.
+    pub base: Option<Rc<String>>,
+    /// The content is only checked against the content model, and the
+    /// values parsed from it are dropped; nothing is reported to the
+    /// parse context.
+    pub check_only: bool,
+}
+impl<'input> ParentContext<'input> {
+    /// Context for parsing a document retrieved from `base`.
+    pub fn with_base(base: &str) -> ParentContext<'input> {
+        UriReference::parse(base).expect(&format!("invalid base URI {:?}", base));
+        ParentContext { base: Some(Rc::new(base.to_string())), ..ParentContext::default() }
+    }
+
+    /// Applies a namespace declaration; `prefix` is empty for a default
+    /// namespace declaration, and an empty `uri` undeclares it.
+    ///
+    /// https://www.w3.org/TR/xml-names/#ns-decl
+    pub fn declare_namespace(&mut self, prefix: &'input str, uri: &'input str) -> Result<(), String> {
+        match (prefix, uri) {
+            ("xml", XML_URI) => (),
+            ("xml", _) => return Err(format!("prefix xml cannot be bound to {:?}", uri)),
+            ("xmlns", _) => return Err("prefix xmlns cannot be declared".to_string()),
+            (_, XML_URI) | (_, XMLNS_URI) => return Err(format!("{:?} cannot be bound to a prefix other than xml", uri)),
+            ("", "") => { self.namespaces.remove(""); },
+            (_, "") => return Err(format!("prefix {} cannot be undeclared", prefix)),
+            _ => { self.namespaces.insert(prefix, uri); },
+        }
+        Ok(())
+    }
+
+    /// Returns the namespace of a prefix, or None if it is not in scope.
+    /// The empty prefix stands for the default namespace, which is absent
+    /// when there is no default namespace declaration.
+    pub fn resolve_prefix(&self, prefix: &str) -> Result<Option<&'input str>, String> {
+        match (prefix, self.namespaces.get(prefix)) {
+            (_, Some(&uri)) => Ok(Some(uri)),
+            ("", None) => Ok(None),
+            (_, None) => Err(format!("unknown namespace prefix {:?}", prefix)),
+        }
+    }
+
+    /// Applies an `xml:base` attribute.
+    ///
+    /// https://www.w3.org/TR/xmlbase/#resolution
+    pub fn set_base(&mut self, xml_base: &str) {
+        let base = match self.base {
+            Some(ref base) => uri::resolve(base, xml_base),
+            None => UriReference::parse(xml_base).map(|_| xml_base.to_string()),
+        };
+        // Invalid xml:base values are ignored.
+        if let Some(base) = base {
+            self.base = Some(Rc::new(base));
+        }
+    }
 }
 impl<'input> Default for ParentContext<'input> {
     fn default() -> ParentContext<'input> {
         let mut namespaces = HashMap::new();
-        namespaces.insert("xmlns", "xmlns");
-        namespaces.insert("xml", "xml");
-        ParentContext { namespaces }
+        namespaces.insert("xml", XML_URI);
+        ParentContext { namespaces, element: None, base: None, check_only: false }
     }
 }
 pub trait ParseContext<'input> {
     fn on_xmlns(&mut self, _name: Option<&'input str>, _uri: &'input str) {
     }
+
+    /// Called with each value of type ID, in the context of the element
+    /// it identifies.
+    fn on_id(&mut self, _id: &'input str, _parent_context: &ParentContext<'input>) {
+    }
+
+    /// Called with each value of type IDREF, and each item of IDREFS values.
+    fn on_idref(&mut self, _idref: &'input str, _parent_context: &ParentContext<'input>) {
+    }
+
+    /// Called when the elements starting at token `index` or after it are
+    /// to be parsed again, so the values reported for them are dropped.
+    fn on_rollback(&mut self, _index: usize) {
+    }
+
+    /// Notations declared by the schemas, which values of NOTATION types
+    /// must name.
+    fn notations(&self) -> &'static [NotationDeclaration] {
+        &[]
+    }
 }
 #[derive(Default)]
 pub struct DefaultParseContext<'input> {
-    _phantom: PhantomData<&'input str>,
+    notations: &'static [NotationDeclaration],
+    ids: IdTable<'input>,
+}
+
+impl<'input> DefaultParseContext<'input> {
+    /// Usually called with the `NOTATIONS` table of the generated code.
+    pub fn with_notations(notations: &'static [NotationDeclaration]) -> DefaultParseContext<'input> {
+        DefaultParseContext { notations, ids: IdTable::default() }
+    }
+
+    pub fn ids(&self) -> &IdTable<'input> {
+        &self.ids
+    }
+
+    pub fn into_ids(self) -> IdTable<'input> {
+        self.ids
+    }
 }
 
 impl<'input> ParseContext<'input> for DefaultParseContext<'input> {
+    fn notations(&self) -> &'static [NotationDeclaration] {
+        self.notations
+    }
+
+    fn on_id(&mut self, id: &'input str, parent_context: &ParentContext<'input>) {
+        self.ids.add_id(id, parent_context);
+    }
+
+    fn on_idref(&mut self, idref: &'input str, parent_context: &ParentContext<'input>) {
+        self.ids.add_idref(idref, parent_context);
+    }
+
+    fn on_rollback(&mut self, index: usize) {
+        self.ids.rollback(index);
+    }
+}
+
+/// Parse context of values which are only parsed to be compared to
+/// others, such as enumeration values; it ignores IDs and IDREFs.
+pub struct CompareParseContext {
+    pub notations: &'static [NotationDeclaration],
+}
+
+impl<'input> ParseContext<'input> for CompareParseContext {
+    fn notations(&self) -> &'static [NotationDeclaration] {
+        self.notations
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#cvc-id
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IdError<'input> {
+    /// Two elements have the same ID.
+    DuplicateId(&'input str),
+    /// An IDREF does not match the ID of any element.
+    DanglingIdref(&'input str),
+}
+
+/// The ID values of a document, and the references to them.
+///
+/// Elements may be parsed several times while trying the alternatives of
+/// a content model, so values are recorded along with the position of
+/// their element, and only count once per element. They are dropped when
+/// their element is rolled back.
+#[derive(Default)]
+pub struct IdTable<'input> {
+    /// Contexts of the elements having each ID, in document order.
+    ids: HashMap<&'input str, Vec<ParentContext<'input>>>,
+    idrefs: HashSet<(&'input str, Option<ElementPosition>)>,
+}
+
+/// Whether the value was reported for an element which is not rolled back
+/// by a rollback to `index`.
+fn is_before(element: Option<ElementPosition>, index: usize) -> bool {
+    element.map_or(true, |element| element.index < index)
+}
+
+impl<'input> IdTable<'input> {
+    fn add_id(&mut self, id: &'input str, parent_context: &ParentContext<'input>) {
+        let contexts = self.ids.entry(id).or_insert_with(Vec::new);
+        if !contexts.iter().any(|context| context.element == parent_context.element) {
+            contexts.push(parent_context.clone());
+        }
+    }
+
+    fn add_idref(&mut self, idref: &'input str, parent_context: &ParentContext<'input>) {
+        self.idrefs.insert((idref, parent_context.element));
+    }
+
+    fn rollback(&mut self, index: usize) {
+        for contexts in self.ids.values_mut() {
+            contexts.retain(|context| is_before(context.element, index));
+        }
+        self.ids.retain(|_, contexts| !contexts.is_empty());
+        self.idrefs.retain(|&(_, element)| is_before(element, index));
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains_key(id)
+    }
+
+    /// Returns the duplicate IDs and the IDREFs matching no ID, once the
+    /// whole document was parsed.
+    pub fn check(&self) -> Result<(), Vec<IdError<'input>>> {
+        let mut errors: Vec<_> = self.ids.iter()
+            .filter(|&(_, contexts)| contexts.len() > 1)
+            .map(|(&id, _)| IdError::DuplicateId(id))
+            .collect();
+        errors.extend(self.idrefs.iter()
+            .filter(|&&(idref, _)| !self.ids.contains_key(idref))
+            .map(|&(idref, _)| IdError::DanglingIdref(idref)));
+        errors.sort();
+        errors.dedup();
+        if errors.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(errors)
+        }
+    }
+
+    /// Parses again the element identified by `id`, from the stream the
+    /// document was parsed from.
+    pub fn get_element<T: ParseXml<'input>, TParseContext: ParseContext<'input>>(&self, id: &str, stream: &mut Stream<'input>, parse_context: &mut TParseContext) -> Option<T> {
+        let parent_context = self.ids.get(id)?.first()?;
+        let position = parent_context.element?;
+        let tx = stream.transaction();
+        stream.index = position.index;
+        stream.depth = position.depth;
+        let element = T::parse_xml(stream, parse_context, parent_context);
+        tx.rollback(stream);
+        element
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#Notation_details
+#[derive(Debug, PartialEq, Eq)]
+pub struct NotationDeclaration {
+    pub namespace: &'static str,
+    pub name: &'static str,
+    pub public: Option<&'static str>,
+    pub system: Option<&'static str>,
 }
 
 pub trait ParseXml<'input>: Sized {
@@ -160,18 +564,513 @@ pub trait ParseXmlStr<'input>: Sized {
     }
 }
 
+/// Splits the value of a list type into its items, collapsing whitespace.
+///
+/// https://www.w3.org/TR/xmlschema11-2/#atomic-vs-list
+pub fn list_items(input: &str) -> Vec<&str> {
+    input.split(is_whitespace).filter(|item| item.len() > 0).collect()
+}
+
+/// Checks the facets of a list type, which apply to the whole list rather
+/// than to its items: lengths count items, and enumeration values and
+/// patterns are compared to the whitespace-collapsed value.
+pub fn list_facets_allow(items: &[&str], facets: &Facets) -> bool {
+    let value = items.join(" ");
+    if let Some(ref enumeration) = facets.enumeration {
+        if !enumeration.iter().any(|allowed| list_items(allowed).join(" ") == value) {
+            return false;
+        }
+    }
+    if !facets.matches_patterns(&value) {
+        return false;
+    }
+    if let Some(length) = facets.length {
+        if items.len() != length {
+            return false;
+        }
+    }
+    if let Some(min_length) = facets.min_length {
+        if items.len() < min_length {
+            return false;
+        }
+    }
+    if let Some(max_length) = facets.max_length {
+        if items.len() > max_length {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks the patterns of a union type, which apply to the lexical value
+/// (with its leading and trailing whitespace stripped) rather than to the
+/// member types. Enumeration values are checked by `impl_union!`, in the
+/// value space of the member types.
+pub fn union_facets_allow(input: &str, facets: &Facets) -> bool {
+    let value = input.trim_matches(is_whitespace);
+    facets.matches_patterns(value)
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#w-process_contents
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProcessContents {
+    Skip,
+    Lax,
+    Strict,
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#nc-variety
+///
+/// By default, absent namespaces are represented by an empty string, as in
+/// generated parsers; the processor stores its own namespaces instead.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NamespaceConstraint<TNamespaces=&'static [&'static str]> {
+    Any,
+    Enumeration(TNamespaces),
+    Not(TNamespaces),
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#Wildcard_details
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Wildcard<TNamespaces=&'static [&'static str], TQNames=&'static [(&'static str, &'static str)]> {
+    pub namespaces: NamespaceConstraint<TNamespaces>,
+    pub not_qnames: TQNames,
+    /// notQName="##defined"
+    pub not_defined: bool,
+    pub process_contents: ProcessContents,
+}
+
+impl Wildcard {
+    /// https://www.w3.org/TR/xmlschema11-1/#cvc-wildcard-name
+    pub fn allows(&self, name: &FullName) -> bool {
+        let namespace = name.namespace().unwrap_or("");
+        let namespace_allowed = match self.namespaces {
+            NamespaceConstraint::Any => true,
+            NamespaceConstraint::Enumeration(namespaces) => namespaces.iter().any(|ns| *ns == namespace),
+            NamespaceConstraint::Not(namespaces) => !namespaces.iter().any(|ns| *ns == namespace),
+        };
+        namespace_allowed && !self.not_qnames.iter().any(|&(ns, local)| ns == namespace && local == name.local_name())
+    }
+}
+
+/// Set of the top-level elements declared by the schemas, used to parse the
+/// content of lax and strict wildcards.
+pub trait ParseKnownElement<'input>: Sized {
+    fn is_known_element(name: &FullName<'input>) -> bool;
+
+    fn parse_known_element<TParseContext: ParseContext<'input>>(name: &FullName<'input>, stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self>;
+}
+
+/// Used as the set of known elements when the schemas declare no top-level
+/// element.
+#[derive(Debug, PartialEq)]
+pub struct NoElement<'input>(PhantomData<&'input ()>);
+
+impl<'input> ParseKnownElement<'input> for NoElement<'input> {
+    fn is_known_element(_name: &FullName<'input>) -> bool {
+        false
+    }
+
+    fn parse_known_element<TParseContext: ParseContext<'input>>(_name: &FullName<'input>, _stream: &mut Stream<'input>, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>) -> Option<Self> {
+        None
+    }
+}
+
+/// An element matched by a wildcard (<xs:any>).
+#[derive(Debug, PartialEq)]
+pub enum AnyElement<'input, TElement> {
+    Known(TElement),
+    Skipped(Any<'input>),
+}
+
+/// Returns the name of the next element in the stream, without consuming it.
+///
+/// Namespace declarations on the element itself are taken into account.
+/// Returns None if there is no element, or if its prefix is not bound.
+pub fn peek_element_name<'input>(stream: &mut Stream<'input>, parent_context: &ParentContext<'input>) -> Option<FullName<'input>> {
+    let tx = stream.transaction();
+    let mut res = None;
+    loop {
+        match stream.next() {
+            Some(XmlToken::Whitespaces(_)) |
+            Some(XmlToken::Comment(_)) |
+            Some(XmlToken::Text(_)) => (),
+            Some(XmlToken::ElementStart(prefix, local)) => {
+                let prefix = prefix.to_str();
+                let mut declared = None;
+                let mut malformed = false;
+                while let Some(XmlToken::Attribute((key_prefix, key_local), value)) = stream.next() {
+                    let declares_prefix = match (key_prefix.to_str(), key_local.to_str()) {
+                        ("xmlns", l) => l == prefix,
+                        ("", "xmlns") => prefix == "",
+                        _ => false,
+                    };
+                    if declares_prefix {
+                        match stream.decode(value.to_str(), TextKind::Attribute) {
+                            Some(uri) => declared = Some(uri),
+                            None => malformed = true,
+                        }
+                    }
+                }
+                let namespace = match declared {
+                    // xmlns="" undeclares the default namespace; prefixes
+                    // cannot be undeclared.
+                    Some("") if prefix == "" => Ok(None),
+                    Some("") => Err(format!("prefix {} cannot be undeclared", prefix)),
+                    Some(uri) => Ok(Some(uri)),
+                    None => parent_context.resolve_prefix(prefix),
+                };
+                if let (false, Ok(namespace)) = (malformed, namespace) {
+                    res = Some(FullName::new(namespace, local.to_str()));
+                }
+                break;
+            },
+            _ => break,
+        }
+    }
+    tx.rollback(stream);
+    res
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#cvc-wildcard-name
+    pub fn allows(&self, name: &FullName) -> bool {
+        let namespace = name.namespace().unwrap_or("");
+        let namespace_allowed = match self.namespaces {
+            NamespaceConstraint::Any => true,
+            NamespaceConstraint::Enumeration(namespaces) => namespaces.iter().any(|ns| *ns == namespace),
+            NamespaceConstraint::Not(namespaces) => !namespaces.iter().any(|ns| *ns == namespace),
+        };
+        namespace_allowed && !self.not_qnames.iter().any(|&(ns, local)| ns == namespace && local == name.local_name())
+    }
+}
+
+/// Set of the top-level elements declared by the schemas, used to parse the
+/// content of lax and strict wildcards.
+pub trait ParseKnownElement<'input>: Sized {
+    fn is_known_element(name: &FullName<'input>) -> bool;
+
+    fn parse_known_element<TParseContext: ParseContext<'input>>(name: &FullName<'input>, stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Self>;
+}
+
+/// Used as the set of known elements when the schemas declare no top-level
+/// element.
+#[derive(Debug, PartialEq)]
+pub struct NoElement<'input>(PhantomData<&'input ()>);
+
+impl<'input> ParseKnownElement<'input> for NoElement<'input> {
+    fn is_known_element(_name: &FullName<'input>) -> bool {
+        false
+    }
+
+    fn parse_known_element<TParseContext: ParseContext<'input>>(_name: &FullName<'input>, _stream: &mut Stream<'input>, _parse_context: &mut TParseContext, _parent_context: &ParentContext<'input>) -> Option<Self> {
+        None
+    }
+}
+
+/// An element matched by a wildcard (<xs:any>).
+#[derive(Debug, PartialEq)]
+pub enum AnyElement<'input, TElement> {
+    Known(TElement),
+    Skipped(Any<'input>),
+}
+
+/// Returns the name of the next element in the stream, without consuming it.
+///
+/// Namespace declarations on the element itself are taken into account.
+pub fn peek_element_name<'input>(stream: &mut Stream<'input>, parent_context: &ParentContext<'input>) -> Option<FullName<'input>> {
+    let tx = stream.transaction();
+    let mut res = None;
+    loop {
+        match stream.next() {
+            Some(XmlToken::Whitespaces(_)) |
+            Some(XmlToken::Comment(_)) |
+            Some(XmlToken::Text(_)) => (),
+            Some(XmlToken::ElementStart(prefix, local)) => {
+                let prefix = prefix.to_str();
+                let mut namespace = parent_context.namespaces.get(prefix).cloned();
+                while let Some(XmlToken::Attribute((key_prefix, key_local), value)) = stream.next() {
+                    match (key_prefix.to_str(), key_local.to_str()) {
+                        ("xmlns", l) if l == prefix => namespace = Some(value.to_str()),
+                        ("", "xmlns") if prefix == "" => {
+                            // xmlns="" undeclares the default namespace.
+                            namespace = match value.to_str() {
+                                "" => None,
+                                uri => Some(uri),
+                            };
+                        },
+                        _ => (),
+                    }
+                }
+                res = Some(FullName::new(namespace, local.to_str()));
+                break;
+            },
+            _ => break,
+        }
+    }
+    tx.rollback(stream);
+    res
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#cvc-wildcard
+pub fn parse_any_element<'input, TElement: ParseKnownElement<'input>, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, wildcard: &Wildcard) -> Option<AnyElement<'input, TElement>> {
+    let name = match peek_element_name(stream, parent_context) {
+        Some(name) => name,
+        None => {
+            // No element; pick up the surrounding character data, if any.
+            let tx = stream.transaction();
+            match Any::parse_xml(stream, parse_context, parent_context) {
+                Some(any) => {
+                    // An element here has an unbound prefix.
+                    let has_element = any.0.iter().any(|tok| match tok { XmlToken::ElementStart(_, _) => true, _ => false });
+                    if !has_element && any.0.iter().any(|tok| match tok { XmlToken::Text(_) | XmlToken::Cdata(_) => true, _ => false }) {
+                        return Some(AnyElement::Skipped(any));
+                    }
+                },
+                None => (),
+            }
+            tx.rollback(stream);
+            return None;
+        }
+    };
+    if !wildcard.allows(&name) {
+        return None;
+    }
+    let known = TElement::is_known_element(&name);
+    if known && wildcard.not_defined {
+        return None;
+    }
+    match (wildcard.process_contents, known) {
+        (ProcessContents::Skip, _) | (ProcessContents::Lax, false) =>
+            Any::parse_xml(stream, parse_context, parent_context).map(AnyElement::Skipped),
+        (ProcessContents::Strict, false) => None,
+        (_, true) =>
+            TElement::parse_known_element(&name, stream, parse_context, parent_context).map(AnyElement::Known),
+    }
+}
+
+/// https://www.w3.org/TR/xmlschema11-1/#oc
+///
+/// Only interleaved open content needs to be known while parsing children;
+/// suffixed open content is parsed like any other trailing field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenContent {
+    pub wildcard: Wildcard,
+    /// Elements of the content model, which are never attributed to the
+    /// open content wildcard.
+    pub siblings: &'static [(&'static str, &'static str)],
+}
+
+impl OpenContent {
+    pub fn allows(&self, name: &FullName) -> bool {
+        let namespace = name.namespace().unwrap_or("");
+        self.wildcard.allows(name) &&
+            !self.siblings.iter().any(|&(ns, local)| ns == namespace && local == name.local_name())
+    }
+}
+
+/// Skips the elements at the current position that belong to the
+/// interleaved open content of their parent, and remembers them so they can
+/// be parsed later by `parse_open_content`.
+pub fn skip_open_content<'input, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) {
+    loop {
+        let open_content = match stream.current_content() {
+            Some(&mut ContentFrame { open_content: Some(open_content), .. }) => open_content,
+            _ => return,
+        };
+        match peek_element_name(stream, parent_context) {
+            Some(ref name) if open_content.allows(name) => (),
+            _ => return,
+        }
+        let start = stream.index;
+        if Any::parse_xml(stream, parse_context, parent_context).is_none() {
+            return;
+        }
+        let end = stream.index;
+        let frame = stream.current_content().expect("content frame disappeared");
+        // Elements skipped at or after this position were skipped by an
+        // attempt which was rolled back.
+        frame.captured.retain(|&(start2, _)| start2 < start);
+        frame.captured.push((start, end));
+    }
+}
+
+/// Parses from `min` to `max` consecutive occurrences of a particle,
+/// rolling back if there are fewer than `min` of them.
+///
+/// https://www.w3.org/TR/xmlschema11-1/#p-min_occurs
+pub fn parse_occurrences<'input, T: ParseXml<'input>, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, min: usize, max: usize) -> Option<Vec<T>> {
+    let tx = stream.transaction();
+    let mut items = Vec::new();
+    while items.len() < max {
+        let index = stream.index;
+        match T::parse_xml(stream, parse_context, parent_context) {
+            Some(item) => items.push(item),
+            None => break,
+        }
+        if stream.index == index && items.len() >= min {
+            // An emptiable particle matched nothing; it would keep doing so.
+            break;
+        }
+    }
+    if items.len() < min {
+        tx.rollback_with(stream, parse_context);
+        return None;
+    }
+    tx.commit();
+    Some(items)
+}
+
+/// Same as `parse_occurrences`, when there is no content left: the
+/// particle has to match the empty content `min` times.
+pub fn parse_empty_occurrences<'input, T: ParseXml<'input>, TParseContext: ParseContext<'input>>(parse_context: &mut TParseContext, parent_context: &ParentContext<'input>, min: usize) -> Option<Vec<T>> {
+    let mut items = Vec::new();
+    while items.len() < min {
+        items.push(T::parse_empty(parse_context, parent_context)?);
+    }
+    Some(items)
+}
+
+/// Parses the elements skipped by `skip_open_content` in the content of
+/// the current element, as well as the ones at the end of its content.
+pub fn parse_open_content<'input, T: ParseXml<'input>, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Vec<T>> {
+    skip_open_content(stream, parse_context, parent_context);
+    let captured = match stream.current_content() {
+        Some(frame) => ::std::mem::replace(&mut frame.captured, Vec::new()),
+        None => return Some(Vec::new()),
+    };
+    let tx = stream.transaction();
+    let mut items = Vec::new();
+    for &(start, end) in &captured {
+        stream.index = start;
+        match T::parse_xml(stream, parse_context, parent_context) {
+            Some(ref _item) if stream.index != end => break,
+            Some(item) => items.push(item),
+            None => break,
+        }
+    }
+    tx.rollback(stream);
+    if items.len() < captured.len() {
+        // Leave the elements to be captured again by another attempt.
+        if let Some(frame) = stream.current_content() {
+            frame.captured = captured;
+        }
+        return None;
+    }
+    Some(items)
+}
+
+/// An item of the content of an element whose content is mixed.
+///
+/// https://www.w3.org/TR/xmlschema11-1/#ct-mixed
+#[derive(Debug, PartialEq)]
+pub enum MixedItem<'input, TElement> {
+    /// Character data, with its references decoded.
+    Text(&'input str),
+    Element(TElement),
+    /// Elements which are not part of the content model, ie. matched by a
+    /// wildcard or by the open content.
+    Other(Any<'input>),
+}
+
+/// Parses the character data and the children of the current element, in
+/// document order. Called once the content was checked against the content
+/// model, so each child is parsed only once into a value.
+pub fn parse_mixed_content<'input, T: ParseKnownElement<'input>, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &ParentContext<'input>) -> Option<Vec<MixedItem<'input, T>>> {
+    let (depth, start) = match stream.current_content() {
+        Some(frame) => (frame.depth, frame.start),
+        None => return Some(Vec::new()),
+    };
+    let end = stream.index;
+    let tx = stream.transaction();
+    stream.index = start;
+    stream.depth = depth;
+    let mut items = Vec::new();
+    while stream.index < end {
+        let tok = stream.tokens[stream.index].clone();
+        let item = match tok {
+            XmlToken::Text(text) | XmlToken::Whitespaces(text) => {
+                stream.index += 1;
+                stream.decode(text.to_str(), TextKind::Content).map(MixedItem::Text)
+            },
+            XmlToken::Cdata(text) => {
+                stream.index += 1;
+                stream.decode(text.to_str(), TextKind::Cdata).map(MixedItem::Text)
+            },
+            XmlToken::ElementStart(_, _) => {
+                match peek_element_name(stream, parent_context) {
+                    Some(ref name) if T::is_known_element(name) =>
+                        T::parse_known_element(name, stream, parse_context, parent_context).map(MixedItem::Element),
+                    Some(_) => Any::parse_xml(stream, parse_context, parent_context).map(MixedItem::Other),
+                    None => None, // Unbound prefix
+                }
+            },
+            _ => {
+                // Comments and processing instructions
+                stream.index += 1;
+                continue;
+            },
+        };
+        match item {
+            Some(item) => items.push(item),
+            None => {
+                tx.rollback(stream);
+                return None;
+            },
+        }
+    }
+    tx.rollback(stream);
+    Some(items)
+}
+
 impl<'input, T> ParseXml<'input> for T where T: ParseXmlStr<'input> {
     const NODE_NAME: &'static str = Self::NODE_NAME;
+    /// Parses simple content: the character data and CDATA sections up to
+    /// the end of the element, which must not have children. Comments and
+    /// processing instructions are dropped, and the character data around
+    /// them is concatenated.
     fn parse_self_xml<'b, TParseContext: ParseContext<'input>>(stream: &mut Stream<'input>, parse_context: &mut TParseContext, parent_context: &'b ParentContext<'input>) -> Option<Self> {
-        match stream.next() {
-            Some(XmlToken::Text(strspan)) => {
-                match Self::parse_self_xml_str(strspan.to_str(), parse_context, parent_context, &Facets::default()) {
-                    Some(("", out)) => Some(out),
-                    Some((unparsed, _)) => None,
-                    None => None,
-                }
+        let tx = stream.transaction();
+        // The first segment is used as is, and only copied if others
+        // follow it.
+        let mut first: Option<&'input str> = None;
+        let mut concatenated: Option<String> = None;
+        loop {
+            let before_token = stream.transaction();
+            let segment = match stream.next() {
+                Some(XmlToken::Comment(_)) |
+                Some(XmlToken::ProcessingInstruction(_, _)) => continue,
+                Some(XmlToken::Text(strspan)) |
+                Some(XmlToken::Whitespaces(strspan)) => stream.decode(strspan.to_str(), TextKind::Content),
+                Some(XmlToken::Cdata(strspan)) => stream.decode(strspan.to_str(), TextKind::Cdata),
+                Some(XmlToken::ElementEnd(ElementEnd::Close(_, _))) => {
+                    // Left for the element to consume.
+                    before_token.rollback(stream);
+                    break;
+                },
+                _ => None, // Child element
+            };
+            let segment = match segment {
+                Some(segment) => segment,
+                None => {
+                    tx.rollback(stream);
+                    return None;
+                },
+            };
+            match first {
+                None => first = Some(segment),
+                Some(first) => concatenated.get_or_insert_with(|| first.to_owned()).push_str(segment),
             }
-            _ => None,
+        }
+        let text = match concatenated {
+            Some(concatenated) => store_decoded(concatenated),
+            None => first.unwrap_or(""),
+        };
+        match Self::parse_self_xml_str(text, parse_context, parent_context, &Facets::default()) {
+            Some(("", out)) => Some(out),
+            _ => {
+                tx.rollback(stream);
+                None
+            },
         }
     }
 }
@@ -22,3 +22,31 @@ fn generated_parses_person_xsd() {
     let (doc, _) = parse_xsd(PERSON_XSD);
     assert_ne!(doc, None);
 }
+
+#[test]
+fn generated_checks_attribute_wildcard_namespace() {
+    // openAttrs allows attributes from ##other namespaces only.
+    let foreign = r#"
+      <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:foo="foo" foo:bar="baz">
+      </xs:schema>"#;
+    let (doc, _) = parse_xsd(foreign);
+    assert_ne!(doc, None);
+
+    let own = r#"
+      <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xs:bar="baz">
+      </xs:schema>"#;
+    let (doc, _) = parse_xsd(own);
+    assert_eq!(doc, None);
+}
+
+#[test]
+fn generated_parses_known_elements_in_appinfo() {
+    let xsd = r#"
+      <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+        <xs:annotation>
+          <xs:appinfo><xs:documentation>foo</xs:documentation><bar /></xs:appinfo>
+        </xs:annotation>
+      </xs:schema>"#;
+    let (doc, _) = parse_xsd(xsd);
+    assert_ne!(doc, None);
+}
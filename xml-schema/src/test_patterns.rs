@@ -0,0 +1,32 @@
+use patterns::{translate, is_match};
+
+#[test]
+fn pattern_matches() {
+    for &(pattern, matching, other) in &[
+        ("\\i\\c*", &["a1", "_x.y", "é"][..], &["1a", "", "a b"][..]),
+        ("[\\i-[:]][\\c-[:]]*", &["ab"][..], &["a:b", ":a"][..]),
+        ("[a-z-[aeiou]]+", &["bcd"][..], &["bad"][..]),
+        ("[^a-[b]]", &["c"][..], &["a", "b"][..]),
+        ("[-a]+|x", &["-a-", "x"][..], &["b"][..]),
+        ("^a$", &["^a$"][..], &["a"][..]),
+        ("a.c", &["abc"][..], &["a\nc"][..]),
+        ("\\p{Lu}\\d{2,3}", &["A12", "B123"][..], &["a12", "A1"][..]),
+        ("(a|b){2,}", &["ab", "bba"][..], &["a"][..]),
+        ("\\w+", &["abc"][..], &["a b", "a-b"][..]),
+    ] {
+        for value in matching {
+            assert!(is_match(pattern, value), "{:?} should match {:?}", pattern, value);
+        }
+        for value in other {
+            assert!(!is_match(pattern, value), "{:?} should not match {:?}", pattern, value);
+        }
+    }
+}
+
+#[test]
+fn invalid_patterns() {
+    for pattern in &["[a", "[]", "[a-b-c]", "[z-a]", "(a", "a)", "a**", "{1}", "a{3,2}", "\\q", "\\p{Foo}"] {
+        assert!(translate(pattern).is_err(), "{:?}", pattern);
+    }
+    assert_eq!(translate("\\p{IsBasicLatin}"), Err("block escape \\p{IsBasicLatin} is not supported".to_string()));
+}
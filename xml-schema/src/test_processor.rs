@@ -0,0 +1,531 @@
+use parse_xsd;
+use processor::Processor;
+
+fn process(xsd: &str) {
+    let (doc, _) = parse_xsd(xsd);
+    let doc = doc.unwrap();
+    let mut proc = Processor::new(&doc);
+    proc.process_ast(&doc);
+}
+
+fn check_upa(xsd: &str) {
+    let (doc, _) = parse_xsd(xsd);
+    let doc = doc.unwrap();
+    let mut proc = Processor::new(&doc);
+    proc.process_ast(&doc);
+    proc.check_unique_particle_attribution(xsd);
+}
+
+#[test]
+fn upa_accepts_deterministic_models() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:element name="a" type="xs:string" minOccurs="0" maxOccurs="unbounded" />
+          <xs:element name="b" type="xs:string" />
+          <xs:choice minOccurs="0">
+            <xs:element name="a" type="xs:string" />
+            <xs:element name="c" type="xs:string" />
+          </xs:choice>
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "element a (line 6, column 29) and element a (line 7, column 29) compete.")]
+fn upa_refuses_optional_element_followed_by_same_name() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:element name="a" type="xs:string" minOccurs="0" />
+          <xs:element name="a" type="xs:int" />
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "violate Unique Particle Attribution")]
+fn upa_refuses_ambiguous_choice() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:choice>
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+            <xs:element name="b" type="xs:string" />
+          </xs:sequence>
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+            <xs:element name="c" type="xs:string" />
+          </xs:sequence>
+        </xs:choice>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+fn upa_accepts_counted_repetition_followed_by_same_name() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:element name="a" type="xs:string" minOccurs="2" maxOccurs="2" />
+          <xs:element name="a" type="xs:int" />
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "element a (line 6, column 29) and element a (line 7, column 29) compete.")]
+fn upa_refuses_optional_repetition_followed_by_same_name() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:element name="a" type="xs:string" minOccurs="1" maxOccurs="2" />
+          <xs:element name="a" type="xs:int" />
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "wildcard Any in the declaration of open (line 3, column 27) and wildcard Any in the declaration of open (line 3, column 27) compete.")]
+fn upa_refuses_overlapping_wildcards() {
+    check_upa(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:complexType name="open">
+      <xs:sequence>
+        <xs:any minOccurs="0" />
+        <xs:any />
+      </xs:sequence>
+    </xs:complexType>
+  </xs:schema>"#);
+}
+
+const RESTRICTION_BASE: &'static str = r#"
+    <xs:complexType name="base">
+      <xs:sequence>
+        <xs:element name="a" type="xs:string" />
+        <xs:element name="b" type="xs:string" minOccurs="0" />
+      </xs:sequence>
+      <xs:attribute name="x" type="xs:string" use="required" />
+      <xs:attribute name="y" type="xs:string" />
+    </xs:complexType>"#;
+
+#[test]
+fn restriction_accepts_subset() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+          </xs:sequence>
+          <xs:attribute name="y" use="prohibited" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: element c is not allowed by the base at this point")]
+fn restriction_refuses_new_element() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+            <xs:element name="c" type="xs:string" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: attribute x is required by the base")]
+fn restriction_refuses_widened_attribute_use() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+          </xs:sequence>
+          <xs:attribute name="x" type="xs:string" use="optional" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: the type of element a is not derived from the base's")]
+fn restriction_refuses_unrelated_element_type() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:int" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+fn restriction_accepts_derived_attribute_type() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+          </xs:sequence>
+          <xs:attribute name="y" type="xs:token" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: the type of attribute y is not derived from the base's")]
+fn restriction_refuses_unrelated_attribute_type() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="xs:string" />
+          </xs:sequence>
+          <xs:attribute name="y" type="xs:int" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: the type of element a is not derived from the base's")]
+fn restriction_refuses_element_type_of_other_schema() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:o="urn:other">
+    <xs:import namespace="urn:other" />{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="a" type="o:text" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, RESTRICTION_BASE));
+}
+
+const WILDCARD_BASE: &'static str = r#"
+    <xs:complexType name="base">
+      <xs:sequence>
+        <xs:any processContents="lax" />
+      </xs:sequence>
+      <xs:anyAttribute processContents="lax" />
+    </xs:complexType>"#;
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: wildcard Any processes its content less strictly than the base's")]
+fn restriction_refuses_weaker_wildcard() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:any processContents="skip" />
+          </xs:sequence>
+          <xs:anyAttribute processContents="strict" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, WILDCARD_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: attribute wildcard Any processes its content less strictly than the base's")]
+fn restriction_refuses_weaker_attribute_wildcard() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:any processContents="strict" />
+          </xs:sequence>
+          <xs:anyAttribute processContents="skip" />
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, WILDCARD_BASE));
+}
+
+const COUNTED_BASE: &'static str = r#"
+    <xs:complexType name="base">
+      <xs:sequence>
+        <xs:element name="n" type="xs:integer" minOccurs="2" maxOccurs="5" />
+      </xs:sequence>
+    </xs:complexType>"#;
+
+#[test]
+fn restriction_accepts_narrowed_occurrences() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="n" type="xs:int" minOccurs="3" maxOccurs="4" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, COUNTED_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: element n is not allowed by the base at this point")]
+fn restriction_refuses_widened_max_occurs() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="n" type="xs:integer" minOccurs="2" maxOccurs="unbounded" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, COUNTED_BASE));
+}
+
+#[test]
+#[should_panic(expected = "type derived is not a valid restriction of base: the content may end before the base's does")]
+fn restriction_refuses_widened_min_occurs() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:complexType name="derived">
+      <xs:complexContent>
+        <xs:restriction base="base">
+          <xs:sequence>
+            <xs:element name="n" type="xs:integer" minOccurs="1" maxOccurs="5" />
+          </xs:sequence>
+        </xs:restriction>
+      </xs:complexContent>
+    </xs:complexType>
+  </xs:schema>"#, COUNTED_BASE));
+}
+
+const FACETS_BASE: &'static str = r#"
+    <xs:simpleType name="base">
+      <xs:restriction base="xs:string">
+        <xs:minLength value="1" />
+        <xs:maxLength value="8" />
+        <xs:whiteSpace value="collapse" fixed="true" />
+        <xs:enumeration value="a" />
+        <xs:enumeration value="bb" />
+        <xs:enumeration value="ccc" />
+      </xs:restriction>
+    </xs:simpleType>"#;
+
+#[test]
+fn facets_accept_narrowing() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:simpleType name="derived">
+      <xs:restriction base="base">
+        <xs:maxLength value="4" />
+        <xs:whiteSpace value="collapse" />
+        <xs:enumeration value="a" />
+        <xs:enumeration value="bb" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#, FACETS_BASE));
+}
+
+#[test]
+#[should_panic(expected = "restriction of base is not valid: maxLength is wider than the base's maxLength")]
+fn facets_refuse_widened_max_length() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:simpleType name="derived">
+      <xs:restriction base="base">
+        <xs:maxLength value="16" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#, FACETS_BASE));
+}
+
+#[test]
+#[should_panic(expected = "restriction of base is not valid: enumeration value \"dddd\" is not allowed by the base")]
+fn facets_refuse_new_enumeration_value() {
+    process(&format!(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}
+    <xs:simpleType name="derived">
+      <xs:restriction base="base">
+        <xs:enumeration value="a" />
+        <xs:enumeration value="dddd" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#, FACETS_BASE));
+}
+
+#[test]
+#[should_panic(expected = "restriction of derived is not valid: maxInclusive is wider than the base's maxInclusive")]
+fn facets_refuse_widened_inherited_bound() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="base">
+      <xs:restriction base="xs:decimal">
+        <xs:maxInclusive value="100" />
+      </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="derived">
+      <xs:restriction base="base">
+        <xs:minInclusive value="0" />
+      </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="derived2">
+      <xs:restriction base="derived">
+        <xs:maxInclusive value="1000" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "restriction of fixed is not valid: maxLength is fixed by the base")]
+fn facets_refuse_changed_fixed_facet() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="fixed">
+      <xs:restriction base="xs:string">
+        <xs:maxLength value="8" fixed="true" />
+      </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="derived">
+      <xs:restriction base="fixed">
+        <xs:maxLength value="4" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "Invalid pattern \"[a\": unclosed character class")]
+fn facets_refuse_invalid_pattern() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="code">
+      <xs:restriction base="xs:string">
+        <xs:pattern value="[a" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "Restriction of NOTATION without an enumeration facet.")]
+fn notation_requires_enumeration() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:notation name="gif" public="image/gif" />
+    <xs:simpleType name="format">
+      <xs:restriction base="xs:NOTATION">
+        <xs:maxLength value="4" />
+      </xs:restriction>
+    </xs:simpleType>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "##defaultNamespace is not supported in wildcards.")]
+fn wildcards_refuse_default_namespace() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:any namespace="##defaultNamespace" />
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "notQName=\"##definedSibling\" is not supported.")]
+fn wildcards_refuse_defined_sibling() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:element name="root">
+      <xs:complexType>
+        <xs:sequence>
+          <xs:element name="a" type="xs:string" />
+          <xs:any notQName="##definedSibling" />
+        </xs:sequence>
+      </xs:complexType>
+    </xs:element>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "cannot be derived by List")]
+fn simple_type_final_refuses_list() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="code" final="list">
+      <xs:restriction base="xs:string" />
+    </xs:simpleType>
+    <xs:simpleType name="codes">
+      <xs:list itemType="code" />
+    </xs:simpleType>
+  </xs:schema>"#);
+}
+
+#[test]
+#[should_panic(expected = "substitution is not a derivation method of simple types.")]
+fn simple_type_final_refuses_substitution() {
+    process(r#"
+  <xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:simpleType name="code" final="substitution">
+      <xs:restriction base="xs:string" />
+    </xs:simpleType>
+  </xs:schema>"#);
+}
@@ -0,0 +1,39 @@
+use uri::{UriReference, resolve};
+
+#[test]
+fn uri_components() {
+    let uri = UriReference::parse("foo://user@example.com:8042/over/there?name=ferret#nose").unwrap();
+    assert_eq!(uri.scheme, Some("foo"));
+    assert_eq!(uri.authority, Some("user@example.com:8042"));
+    assert_eq!(uri.path, "/over/there");
+    assert_eq!(uri.query, Some("name=ferret"));
+    assert_eq!(uri.fragment, Some("nose"));
+
+    let uri = UriReference::parse("../a b/é?").unwrap();
+    assert_eq!(uri.scheme, None);
+    assert_eq!(uri.authority, None);
+    assert_eq!(uri.path, "../a b/é");
+    assert_eq!(uri.query, Some(""));
+    assert_eq!(uri.fragment, None);
+
+    for invalid in &["1a:b", "a%2", "%zz", "#a#b", "http://host:port/", "http://[::1/"] {
+        assert_eq!(UriReference::parse(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn uri_resolution() {
+    // https://tools.ietf.org/html/rfc3986#section-5.4
+    let base = "http://a/b/c/d;p?q";
+    for (reference, expected) in &[
+        ("g:h", "g:h"), ("g", "http://a/b/c/g"), ("./g", "http://a/b/c/g"),
+        ("g/", "http://a/b/c/g/"), ("/g", "http://a/g"), ("//g", "http://g"),
+        ("?y", "http://a/b/c/d;p?y"), ("g?y", "http://a/b/c/g?y"), ("#s", "http://a/b/c/d;p?q#s"),
+        ("", "http://a/b/c/d;p?q"), (".", "http://a/b/c/"), ("..", "http://a/b/"),
+        ("../g", "http://a/b/g"), ("../..", "http://a/"), ("../../../g", "http://a/g"),
+        ("/./g", "http://a/g"), ("g.", "http://a/b/c/g."), ("./g/.", "http://a/b/c/g/"),
+        ("g;x=1/../y", "http://a/b/c/y"), ("g?y/./x", "http://a/b/c/g?y/./x"),
+        ] {
+        assert_eq!(resolve(base, reference).as_ref().map(|s| &s[..]), Some(*expected), "{:?}", reference);
+    }
+}
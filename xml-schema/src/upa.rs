@@ -0,0 +1,372 @@
+//! Unique Particle Attribution.
+//!
+//! Generated parsers try the alternatives of a content model in order and
+//! keep the first one which matches, so an ambiguous content model would
+//! silently be parsed one way. This checks content models are deterministic
+//! before generating parsers for them.
+//!
+//! https://www.w3.org/TR/xmlschema11-1/#cos-nonambig
+
+use std::collections::{BTreeSet, HashSet};
+
+use names::FullName;
+use processor::*;
+use xml_utils::source_location;
+
+/// A particle an element information item can be attributed to.
+#[derive(Debug)]
+pub(crate) enum Term<'input> {
+    /// The declared name, all the names it matches (including its
+    /// substitution group), and the attributes and type of the declaration
+    /// unless it is in an other schema.
+    Element(FullName<'input>, Vec<FullName<'input>>, Option<(Attrs<'input>, Type<'input>)>),
+    Wildcard(Wildcard<'input>),
+}
+
+/// Above this number of positions, counted repetitions are not unrolled
+/// anymore.
+const MAX_UNROLLED_POSITIONS: usize = 10000;
+
+/// Glushkov automaton of a content model, whose positions are particles.
+///
+/// Unless built with `Automaton::exact`, counted repetitions are
+/// approximated as unbounded ones, so models which are only deterministic
+/// thanks to counting (eg. `(a{2}, a)`) would be refused.
+pub(crate) struct Automaton<'input> {
+    pub(crate) terms: Vec<Term<'input>>,
+    pub(crate) follow: Vec<HashSet<usize>>,
+    /// The particle each position is a copy of, as the position of its
+    /// first copy.
+    particles: Vec<usize>,
+    /// The named type, group or element declaration each position was
+    /// found in, if any.
+    declarations: Vec<Option<FullName<'input>>>,
+    /// The declaration whose content model is being built.
+    declaration: Option<FullName<'input>>,
+    /// Whether counted repetitions are unrolled, so the automaton accepts
+    /// exactly the sequences its content model does. Positions are then
+    /// copies of particles, and the automaton may not be deterministic.
+    exact: bool,
+    /// Set when an exact automaton grew too large, and some of its counted
+    /// repetitions were approximated.
+    pub(crate) approximated: bool,
+}
+
+pub(crate) struct Fragment {
+    pub(crate) nullable: bool,
+    pub(crate) first: HashSet<usize>,
+    pub(crate) last: HashSet<usize>,
+}
+
+impl Fragment {
+    fn empty() -> Fragment {
+        Fragment { nullable: true, first: HashSet::new(), last: HashSet::new() }
+    }
+}
+
+impl<'input> Automaton<'input> {
+    pub(crate) fn new() -> Automaton<'input> {
+        Automaton { terms: Vec::new(), follow: Vec::new(), particles: Vec::new(), declarations: Vec::new(), declaration: None, exact: false, approximated: false }
+    }
+
+    pub(crate) fn exact() -> Automaton<'input> {
+        Automaton { exact: true, ..Automaton::new() }
+    }
+
+    fn leaf(&mut self, term: Term<'input>) -> Fragment {
+        let position = self.terms.len();
+        self.terms.push(term);
+        self.follow.push(HashSet::new());
+        self.particles.push(position);
+        self.declarations.push(self.declaration);
+        let mut first = HashSet::new();
+        first.insert(position);
+        Fragment { nullable: false, first: first.clone(), last: first }
+    }
+
+    fn sequence(&mut self, fragments: Vec<Fragment>) -> Fragment {
+        let mut res = Fragment::empty();
+        for fragment in fragments {
+            for &position in res.last.iter() {
+                self.follow[position].extend(fragment.first.iter().cloned());
+            }
+            if res.nullable {
+                res.first.extend(fragment.first);
+            }
+            if fragment.nullable {
+                res.last.extend(fragment.last);
+            }
+            else {
+                res.last = fragment.last;
+            }
+            res.nullable = res.nullable && fragment.nullable;
+        }
+        res
+    }
+
+    fn choice(&mut self, fragments: Vec<Fragment>) -> Fragment {
+        if fragments.is_empty() {
+            return Fragment::empty();
+        }
+        let mut res = Fragment { nullable: false, first: HashSet::new(), last: HashSet::new() };
+        for fragment in fragments {
+            res.nullable = res.nullable || fragment.nullable;
+            res.first.extend(fragment.first);
+            res.last.extend(fragment.last);
+        }
+        res
+    }
+
+    fn occurs(&mut self, mut fragment: Fragment, min_occurs: usize, max_occurs: usize) -> Fragment {
+        if max_occurs == 0 {
+            return Fragment::empty();
+        }
+        if max_occurs > 1 {
+            for &position in fragment.last.iter() {
+                self.follow[position].extend(fragment.first.iter().cloned());
+            }
+        }
+        if min_occurs == 0 {
+            fragment.nullable = true;
+        }
+        fragment
+    }
+
+    /// Repeats the fragment built by `build`, which is called once per
+    /// copy when counted repetitions are unrolled.
+    fn repeat<F>(&mut self, min_occurs: usize, max_occurs: usize, mut build: F) -> Fragment
+            where F: FnMut(&mut Automaton<'input>) -> Fragment {
+        let unbounded = max_occurs == usize::max_value();
+        let counted = min_occurs > 1 || (max_occurs > 1 && !unbounded);
+        if !self.exact || !counted || self.approximated {
+            let fragment = build(self);
+            return self.occurs(fragment, min_occurs, max_occurs);
+        }
+        // eg. a{2,4} is unrolled as (a, a, a?, a?), and a{2,} as (a, a+).
+        let copies = if unbounded { min_occurs } else { max_occurs };
+        let mut fragments = Vec::new();
+        let first_copy = self.terms.len();
+        let mut copy_len = 0;
+        for i in 0..copies {
+            let copy = self.terms.len();
+            let fragment = build(self);
+            if i == 0 {
+                copy_len = self.terms.len() - copy;
+            }
+            else if self.terms.len() - copy == copy_len {
+                for offset in 0..copy_len {
+                    self.particles[copy + offset] = self.particles[first_copy + offset];
+                }
+            }
+            let fragment = if i >= min_occurs {
+                self.occurs(fragment, 0, 1)
+            }
+            else if unbounded && i + 1 == copies {
+                self.occurs(fragment, 1, max_occurs)
+            }
+            else {
+                fragment
+            };
+            fragments.push(fragment);
+            if self.terms.len() > MAX_UNROLLED_POSITIONS {
+                self.approximated = true;
+                let fragment = self.sequence(fragments);
+                return self.occurs(fragment, min_occurs.min(1), max_occurs);
+            }
+        }
+        self.sequence(fragments)
+    }
+
+    /// Element particles compete when their names overlap, and wildcards
+    /// when their namespaces do. Element particles take precedence over
+    /// wildcards, so they never compete with them; nor do the copies of a
+    /// particle made by unrolling counted repetitions.
+    fn compete(&self, position1: usize, position2: usize) -> bool {
+        if self.particles[position1] == self.particles[position2] {
+            return false;
+        }
+        match (&self.terms[position1], &self.terms[position2]) {
+            (Term::Element(_, names1, _), Term::Element(_, names2, _)) =>
+                names1.iter().any(|name| names2.contains(name)),
+            (Term::Wildcard(w1), Term::Wildcard(w2)) => w1.overlaps(w2),
+            _ => false,
+        }
+    }
+
+    /// Returns the pairs of competing particles.
+    fn conflicts(&self, start: &HashSet<usize>) -> Vec<(usize, usize)> {
+        let mut conflicts = Vec::new();
+        for positions in Some(start).into_iter().chain(self.follow.iter()) {
+            let mut positions: Vec<_> = positions.iter().cloned().collect();
+            positions.sort();
+            for (i, &position1) in positions.iter().enumerate() {
+                for &position2 in &positions[i+1..] {
+                    if self.compete(position1, position2) {
+                        conflicts.push((position1, position2));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    fn describe(&self, position: usize, source: &str) -> String {
+        match &self.terms[position] {
+            Term::Element(name, _, _) => {
+                match source_location(source, name.local_name()) {
+                    Some((line, column)) => format!("element {} (line {}, column {})", name, line, column),
+                    None => format!("element {}", name),
+                }
+            },
+            Term::Wildcard(wildcard) => {
+                // Wildcards have no name to locate them by, so they are
+                // located by the declaration they appear in.
+                let declaration = self.declarations[position];
+                match declaration.and_then(|name| source_location(source, name.local_name()).map(|location| (name, location))) {
+                    Some((name, (line, column))) => format!("wildcard {:?} in the declaration of {} (line {}, column {})", wildcard.namespaces, name, line, column),
+                    None => format!("wildcard {:?}", wildcard.namespaces),
+                }
+            },
+        }
+    }
+
+    /// Builds the content model of the declaration `name` with `build`.
+    fn in_declaration<F>(&mut self, name: FullName<'input>, build: F) -> Fragment
+            where F: FnOnce(&mut Automaton<'input>) -> Fragment {
+        let outer = ::std::mem::replace(&mut self.declaration, Some(name));
+        let fragment = build(self);
+        self.declaration = outer;
+        fragment
+    }
+}
+
+impl<'ast, 'input: 'ast> Processor<'ast, 'input> {
+    /// Panics if any content model of the schema violates the Unique
+    /// Particle Attribution constraint. `source` is the text of the schema,
+    /// used to locate the competing particles.
+    ///
+    /// Choices and sequences are checked as part of the type, group or
+    /// element declarations they appear in.
+    pub fn check_unique_particle_attribution(&self, source: &str) {
+        let mut violations = BTreeSet::new();
+        let mut models: Vec<(FullName<'input>, &Type<'input>)> = Vec::new();
+        models.extend(self.groups.iter().map(|(&name, group)| (name, &group.type_)));
+        models.extend(self.types.iter().map(|(&name, type_)| (name, &type_.type_)));
+        models.extend(self.elements.iter().map(|(&name, element)| (name, &element.type_)));
+        models.extend(self.inline_elements.keys().map(|&(namespace, local_name, _, ref type_)| (FullName::new(namespace, local_name), type_)));
+        for (name, model) in models {
+            self.check_model(source, &mut violations, |automaton| {
+                automaton.in_declaration(name, |automaton| self.build_automaton(automaton, model))
+            });
+        }
+        if !violations.is_empty() {
+            let violations: Vec<_> = violations.into_iter().collect();
+            panic!("Content models violate Unique Particle Attribution:\n{}", violations.join("\n"));
+        }
+    }
+
+    /// Whether the first element of `type_` may be matched by a wildcard.
+    /// Generated parsers try such alternatives of a choice last, as element
+    /// particles take precedence over wildcards.
+    pub(crate) fn may_start_with_wildcard(&self, type_: &Type<'input>) -> bool {
+        let mut automaton = Automaton::new();
+        let fragment = self.build_automaton(&mut automaton, type_);
+        fragment.first.iter().any(|&position| match automaton.terms[position] {
+            Term::Wildcard(_) => true,
+            Term::Element(_, _, _) => false,
+        })
+    }
+
+    fn check_model<F>(&self, source: &str, violations: &mut BTreeSet<String>, build: F)
+            where F: FnOnce(&mut Automaton<'input>) -> Fragment {
+        // Counted repetitions are unrolled, so particles only compete when
+        // the counts do not tell them apart.
+        let mut automaton = Automaton::exact();
+        let fragment = build(&mut automaton);
+        for (position1, position2) in automaton.conflicts(&fragment.first) {
+            violations.insert(format!("{} and {} compete.",
+                automaton.describe(position1, source), automaton.describe(position2, source)));
+        }
+    }
+
+    fn build_choice(&self, automaton: &mut Automaton<'input>, items: &[RichType<'input, Type<'input>>]) -> Fragment {
+        let fragments = items.iter().map(|item| self.build_automaton(automaton, &item.type_)).collect();
+        automaton.choice(fragments)
+    }
+
+    fn build_sequence(&self, automaton: &mut Automaton<'input>, items: &[RichType<'input, Type<'input>>]) -> Fragment {
+        let fragments = items.iter().map(|item| self.build_automaton(automaton, &item.type_)).collect();
+        automaton.sequence(fragments)
+    }
+
+    pub(crate) fn build_automaton(&self, automaton: &mut Automaton<'input>, type_: &Type<'input>) -> Fragment {
+        match type_ {
+            Type::Any(wildcard) => automaton.leaf(Term::Wildcard(wildcard.clone())),
+            Type::Empty | Type::Simple(_) => Fragment::empty(),
+            Type::Alias(name) => match self.types.get(name) {
+                Some(target) => automaton.in_declaration(*name, |automaton| self.build_automaton(automaton, &target.type_)),
+                None => Fragment::empty(), // Declared in an other schema
+            },
+            Type::Extension(base, ext_type) => {
+                let base = self.build_automaton(automaton, &Type::Alias(*base));
+                let ext = self.build_automaton(automaton, &ext_type.type_);
+                automaton.sequence(vec![base, ext])
+            },
+            Type::Restriction(_, ty) | Type::OpenContent(_, ty) | Type::Mixed(ty) => self.build_automaton(automaton, &ty.type_),
+            Type::ElementRef(min_occurs, max_occurs, name) => {
+                let mut names = Vec::new();
+                self.substitutable_names(name, &mut names);
+                let declaration = self.elements.get(name).map(|element| (element.attrs.clone(), element.type_.clone()));
+                automaton.repeat(*min_occurs, *max_occurs, |automaton| {
+                    automaton.leaf(Term::Element(*name, names.clone(), declaration.clone()))
+                })
+            },
+            Type::Element(min_occurs, max_occurs, struct_name) => {
+                let (name, declaration) = self.inline_elements.iter()
+                    .find(|&(_, (struct_names, _))| struct_names.contains(struct_name))
+                    .map(|(&(namespace, local_name, ref attrs, ref type_), _)| (FullName::new(namespace, local_name), (attrs.clone(), type_.clone())))
+                    .expect(&format!("Unknown element {:?}", struct_name));
+                automaton.repeat(*min_occurs, *max_occurs, |automaton| {
+                    automaton.leaf(Term::Element(name, vec![name], Some(declaration.clone())))
+                })
+            },
+            Type::Group(min_occurs, max_occurs, name) => match self.groups.get(name) {
+                Some(group) => automaton.repeat(*min_occurs, *max_occurs, |automaton| {
+                    automaton.in_declaration(*name, |automaton| self.build_automaton(automaton, &group.type_))
+                }),
+                None => Fragment::empty(), // Declared in an other schema
+            },
+            Type::Choice(min_occurs, max_occurs, struct_name) => {
+                let items = self.choices.iter()
+                    .find(|&(_, struct_names)| struct_names.contains(struct_name))
+                    .map(|(items, _)| items)
+                    .expect(&format!("Unknown choice {:?}", struct_name));
+                automaton.repeat(*min_occurs, *max_occurs, |automaton| self.build_choice(automaton, items))
+            },
+            Type::InlineChoice(items) => self.build_choice(automaton, items),
+            Type::Sequence(min_occurs, max_occurs, struct_name) => {
+                let items = self.sequences.iter()
+                    .find(|&(_, (struct_names, _))| struct_names.contains(struct_name))
+                    .map(|(items, _)| items)
+                    .expect(&format!("Unknown sequence {:?}", struct_name));
+                automaton.repeat(*min_occurs, *max_occurs, |automaton| self.build_sequence(automaton, items))
+            },
+            Type::InlineSequence(items) => self.build_sequence(automaton, items),
+        }
+    }
+
+    /// Names of the elements which may appear where `name` is referenced.
+    fn substitutable_names(&self, name: &FullName<'input>, names: &mut Vec<FullName<'input>>) {
+        if names.contains(name) {
+            return;
+        }
+        if !self.abstract_elements.contains(name) {
+            names.push(*name);
+        }
+        if let Some(members) = self.substitution_groups.get(name) {
+            for member in members {
+                self.substitutable_names(member, names);
+            }
+        }
+    }
+}
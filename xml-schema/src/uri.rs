@@ -0,0 +1,252 @@
+//! URI references (RFC 3986) and IRI references (RFC 3987), as used by
+//! `anyURI` values and `xml:base`.
+
+use std::fmt;
+
+/// The components of a URI or IRI reference.
+///
+/// https://tools.ietf.org/html/rfc3986#section-3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UriReference<'a> {
+    pub scheme: Option<&'a str>,
+    pub authority: Option<&'a str>,
+    pub path: &'a str,
+    pub query: Option<&'a str>,
+    pub fragment: Option<&'a str>,
+}
+
+impl<'a> UriReference<'a> {
+    /// Splits a reference into its components, following
+    /// https://tools.ietf.org/html/rfc3986#appendix-B
+    ///
+    /// Validation is lenient, as asked by XML Schema 1.1: characters that
+    /// should have been escaped (non-ASCII, `<`, ...) are accepted, but
+    /// schemes, ports, percent-encodings and delimiters must be well-formed.
+    pub fn parse(s: &'a str) -> Option<UriReference<'a>> {
+        let mut rest = s;
+        let mut scheme = None;
+        if let Some(i) = rest.find(|c: char| c == ':' || c == '/' || c == '?' || c == '#') {
+            if &rest[i..i+1] == ":" {
+                // A colon in the first segment of a relative reference
+                // would be mistaken for a scheme.
+                if !is_scheme(&rest[0..i]) {
+                    return None;
+                }
+                scheme = Some(&rest[0..i]);
+                rest = &rest[i+1..];
+            }
+        }
+        let mut authority = None;
+        if rest.starts_with("//") {
+            let end = rest[2..].find(|c: char| c == '/' || c == '?' || c == '#').map_or(rest.len(), |i| i + 2);
+            if !is_authority(&rest[2..end]) {
+                return None;
+            }
+            authority = Some(&rest[2..end]);
+            rest = &rest[end..];
+        }
+        let end = rest.find(|c: char| c == '?' || c == '#').unwrap_or(rest.len());
+        let path = &rest[0..end];
+        rest = &rest[end..];
+        let mut query = None;
+        if rest.starts_with('?') {
+            let end = rest.find('#').unwrap_or(rest.len());
+            query = Some(&rest[1..end]);
+            rest = &rest[end..];
+        }
+        let mut fragment = None;
+        if rest.starts_with('#') {
+            if rest[1..].contains('#') {
+                return None;
+            }
+            fragment = Some(&rest[1..]);
+        }
+        if ![path, query.unwrap_or(""), fragment.unwrap_or("")].iter().all(|s| is_percent_encoded(s)) {
+            return None;
+        }
+        Some(UriReference { scheme, authority, path, query, fragment })
+    }
+
+    /// Resolves this reference against a base URI.
+    ///
+    /// https://tools.ietf.org/html/rfc3986#section-5.2.2
+    pub fn resolve(&self, base: &UriReference<'a>) -> String {
+        let scheme;
+        let authority;
+        let path;
+        let query;
+        if self.scheme.is_some() {
+            scheme = self.scheme;
+            authority = self.authority;
+            path = remove_dot_segments(self.path);
+            query = self.query;
+        }
+        else {
+            scheme = base.scheme;
+            if self.authority.is_some() {
+                authority = self.authority;
+                path = remove_dot_segments(self.path);
+                query = self.query;
+            }
+            else {
+                authority = base.authority;
+                if self.path == "" {
+                    path = base.path.to_string();
+                    query = self.query.or(base.query);
+                }
+                else {
+                    if self.path.starts_with('/') {
+                        path = remove_dot_segments(self.path);
+                    }
+                    else {
+                        path = remove_dot_segments(&merge(base, self.path));
+                    }
+                    query = self.query;
+                }
+            }
+        }
+        let target = UriReference { scheme, authority, path: &path, query, fragment: self.fragment };
+        target.to_string()
+    }
+}
+
+impl<'a> fmt::Display for UriReference<'a> {
+    /// https://tools.ietf.org/html/rfc3986#section-5.3
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(scheme) = self.scheme {
+            write!(f, "{}:", scheme)?;
+        }
+        if let Some(authority) = self.authority {
+            write!(f, "//{}", authority)?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves `reference` against `base`, or returns None if either of
+/// them is not a valid reference.
+pub fn resolve(base: &str, reference: &str) -> Option<String> {
+    let base = UriReference::parse(base)?;
+    let reference = UriReference::parse(reference)?;
+    Some(reference.resolve(&base))
+}
+
+/// https://tools.ietf.org/html/rfc3986#section-3.1
+fn is_scheme(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => (),
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+}
+
+/// https://tools.ietf.org/html/rfc3986#section-3.2
+fn is_authority(s: &str) -> bool {
+    let (userinfo, host_port) = match s.rfind('@') {
+        Some(i) => (&s[0..i], &s[i+1..]),
+        None => ("", s),
+    };
+    let port = if host_port.starts_with('[') {
+        // IP-literal
+        let end = match host_port.find(']') {
+            Some(end) => end,
+            None => return false,
+        };
+        match &host_port[end+1..] {
+            "" => "",
+            rest if rest.starts_with(':') => &rest[1..],
+            _ => return false,
+        }
+    }
+    else {
+        match host_port.rfind(':') {
+            Some(i) => &host_port[i+1..],
+            None => "",
+        }
+    };
+    if !host_port.starts_with('[') && host_port.contains(|c: char| c == '[' || c == ']') {
+        return false;
+    }
+    port.chars().all(|c| c.is_ascii_digit()) && is_percent_encoded(userinfo) && is_percent_encoded(host_port)
+}
+
+/// Checks every `%` starts a percent-encoded octet.
+///
+/// https://tools.ietf.org/html/rfc3986#section-2.1
+fn is_percent_encoded(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if i + 2 >= bytes.len() || !bytes[i+1].is_ascii_hexdigit() || !bytes[i+2].is_ascii_hexdigit() {
+                return false;
+            }
+            i += 3;
+        }
+        else {
+            i += 1;
+        }
+    }
+    true
+}
+
+/// https://tools.ietf.org/html/rfc3986#section-5.2.3
+fn merge(base: &UriReference, path: &str) -> String {
+    if base.authority.is_some() && base.path == "" {
+        format!("/{}", path)
+    }
+    else {
+        match base.path.rfind('/') {
+            Some(i) => format!("{}{}", &base.path[0..i+1], path),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// https://tools.ietf.org/html/rfc3986#section-5.2.4
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while input.len() > 0 {
+        if input.starts_with("../") {
+            input = &input[3..];
+        }
+        else if input.starts_with("./") || input.starts_with("/./") {
+            input = &input[2..];
+        }
+        else if input == "/." {
+            input = "/";
+        }
+        else if input.starts_with("/../") {
+            input = &input[3..];
+            pop_segment(&mut output);
+        }
+        else if input == "/.." {
+            input = "/";
+            pop_segment(&mut output);
+        }
+        else if input == "." || input == ".." {
+            input = "";
+        }
+        else {
+            let start = if input.starts_with('/') { 1 } else { 0 };
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push_str(&input[0..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
+
+fn pop_segment(output: &mut String) {
+    let end = output.rfind('/').unwrap_or(0);
+    output.truncate(end);
+}
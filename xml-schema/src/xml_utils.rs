@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 /// https://www.w3.org/TR/xml11/#NT-Char
 #[inline(always)]
 pub fn is_xml_char(c: char) -> bool {
@@ -29,3 +32,228 @@ pub fn is_name_char(c: char) -> bool {
         _ => is_name_start_char(c),
     }
 }
+
+/// https://www.w3.org/TR/xml11/#NT-S
+#[inline(always)]
+pub fn is_whitespace(c: char) -> bool {
+    match c {
+        ' ' | '\t' | '\n' | '\r' => true,
+        _ => false,
+    }
+}
+
+/// https://www.w3.org/TR/xml11/#sec-predefined-ent
+fn predefined_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "apos" => Some('\''),
+        "quot" => Some('"'),
+        _ => None,
+    }
+}
+
+/// Returns the character a reference stands for, given the text between
+/// its `&` and `;`.
+///
+/// https://www.w3.org/TR/xml11/#NT-Reference
+fn decode_reference(reference: &str) -> Option<char> {
+    let (digits, radix) = if reference.starts_with("#x") {
+        (&reference[2..], 16)
+    }
+    else if reference.starts_with('#') {
+        (&reference[1..], 10)
+    }
+    else {
+        return predefined_entity(reference);
+    };
+    // from_str_radix would also accept a sign.
+    if digits.len() == 0 || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    match u32::from_str_radix(digits, radix).ok().and_then(::std::char::from_u32) {
+        Some('\0') | None => None,
+        Some(c) if is_xml_char(c) => Some(c),
+        Some(_) => None,
+    }
+}
+
+/// Where character data was written, which decides how it is decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextKind {
+    /// Element content, as written in the document: references and
+    /// CDATA sections.
+    Content,
+    /// An attribute value, as written in the document.
+    Attribute,
+    /// The content of a CDATA section, which is taken literally.
+    Cdata,
+}
+
+const CDATA_START: &'static str = "<![CDATA[";
+const CDATA_END: &'static str = "]]>";
+
+/// How many entity references may be nested in the replacement texts of
+/// other entities.
+pub const MAX_ENTITY_DEPTH: usize = 16;
+/// How many bytes expanding the entity references of a document may
+/// produce, so documents nesting references to multiply their size
+/// ("billion laughs") are rejected early.
+pub const MAX_ENTITY_EXPANSION: usize = 1 << 20;
+
+/// Builds the replacement text of an internal entity from its literal
+/// value: character references are replaced by the characters they stand
+/// for and line ends are normalized, but entity references are left as
+/// they are, to be expanded where the entity is referenced.
+///
+/// Returns None if a character reference is malformed.
+///
+/// https://www.w3.org/TR/xml11/#intern-replacement
+pub fn decode_entity_value(raw: &str) -> Option<Cow<str>> {
+    if !raw.contains(|c| c == '&' || c == '\r') {
+        return Some(Cow::Borrowed(raw));
+    }
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(i) = rest.find(|c| c == '&' || c == '\r') {
+        decoded.push_str(&rest[0..i]);
+        if rest.as_bytes()[i] == b'\r' {
+            decoded.push('\n');
+            rest = if rest[i+1..].starts_with('\n') { &rest[i+2..] } else { &rest[i+1..] };
+            continue;
+        }
+        let end = i + rest[i..].find(';')?;
+        let reference = &rest[i+1..end];
+        if reference.starts_with('#') {
+            decoded.push(decode_reference(reference)?);
+        }
+        else {
+            decoded.push_str(&rest[i..end+1]);
+        }
+        rest = &rest[end+1..];
+    }
+    decoded.push_str(rest);
+    Some(Cow::Owned(decoded))
+}
+
+/// Replaces the entity and character references of character data by
+/// the characters they stand for, and normalizes line ends.
+///
+/// `entities` are the replacement texts of the internal general entities
+/// declared by the document (see `decode_entity_value`), whose references
+/// are expanded in turn. In element content, CDATA sections are replaced
+/// by their content. In attribute values, whitespace characters written as
+/// such are replaced by spaces, but not those written as character
+/// references.
+///
+/// `budget` is how many more bytes expanding entity references may produce
+/// in the document; it is shared by all its values.
+///
+/// Returns None if a reference is malformed or names an unknown entity, if
+/// entity references are nested or expanded beyond the limits, or if a
+/// replacement text brings a `<` into an attribute value.
+///
+/// https://www.w3.org/TR/xml11/#sec-line-ends
+/// https://www.w3.org/TR/xml11/#AVNormalize
+/// https://www.w3.org/TR/xml11/#intern-replacement
+pub fn decode_text<'a>(raw: &'a str, kind: TextKind, entities: &HashMap<&str, &str>, budget: &mut usize) -> Option<Cow<'a, str>> {
+    if !raw.contains(|c| is_special(kind, c)) {
+        return Some(Cow::Borrowed(raw));
+    }
+    let mut decoded = String::with_capacity(raw.len());
+    decode_into(&mut decoded, raw, kind, entities, 0, budget)?;
+    Some(Cow::Owned(decoded))
+}
+
+fn is_special(kind: TextKind, c: char) -> bool {
+    match (kind, c) {
+        (_, '\r') => true,
+        (TextKind::Content, '&') | (TextKind::Content, '<') => true,
+        (TextKind::Attribute, '&') | (TextKind::Attribute, '<') => true,
+        (TextKind::Attribute, '\t') | (TextKind::Attribute, '\n') => true,
+        _ => false,
+    }
+}
+
+/// Appends text to a decoded value; text coming from replacement texts
+/// (ie. at a positive depth) is counted against the budget.
+fn append(decoded: &mut String, text: &str, depth: usize, budget: &mut usize) -> Option<()> {
+    if depth > 0 {
+        *budget = budget.checked_sub(text.len())?;
+    }
+    decoded.push_str(text);
+    Some(())
+}
+
+fn decode_into(decoded: &mut String, raw: &str, kind: TextKind, entities: &HashMap<&str, &str>, depth: usize, budget: &mut usize) -> Option<()> {
+    let mut rest = raw;
+    while let Some(i) = rest.find(|c| is_special(kind, c)) {
+        append(decoded, &rest[0..i], depth, budget)?;
+        match rest.as_bytes()[i] {
+            b'&' => {
+                let end = i + rest[i..].find(';')?;
+                let reference = &rest[i+1..end];
+                match decode_reference(reference) {
+                    Some(c) => append(decoded, c.encode_utf8(&mut [0; 4]), depth, budget)?,
+                    None => {
+                        let replacement_text = entities.get(reference)?;
+                        if depth >= MAX_ENTITY_DEPTH {
+                            return None;
+                        }
+                        decode_into(decoded, replacement_text, kind, entities, depth + 1, budget)?;
+                    },
+                }
+                rest = &rest[end+1..];
+            },
+            b'<' if kind == TextKind::Attribute => {
+                // Only replacement texts may bring it, and they may not in
+                // attribute values.
+                // https://www.w3.org/TR/xml11/#CleanAttrVals
+                return None;
+            },
+            b'<' => {
+                // Other markup may only come from entities, which is not
+                // supported.
+                if !rest[i..].starts_with(CDATA_START) {
+                    return None;
+                }
+                let start = i + CDATA_START.len();
+                let end = start + rest[start..].find(CDATA_END)?;
+                decode_into(decoded, &rest[start..end], TextKind::Cdata, entities, depth, budget)?;
+                rest = &rest[end+CDATA_END.len()..];
+            },
+            b'\r' if depth > 0 && kind != TextKind::Attribute => {
+                // Line ends of replacement texts are normalized when the
+                // entity is declared, so this one was written as a
+                // character reference.
+                append(decoded, "\r", depth, budget)?;
+                rest = &rest[i+1..];
+            },
+            b'\r' => {
+                // Both "\r\n" and a lone "\r" are line ends.
+                append(decoded, if kind == TextKind::Attribute { " " } else { "\n" }, depth, budget)?;
+                rest = if rest[i+1..].starts_with('\n') { &rest[i+2..] } else { &rest[i+1..] };
+            },
+            _ => {
+                append(decoded, " ", depth, budget)?;
+                rest = &rest[i+1..];
+            },
+        }
+    }
+    append(decoded, rest, depth, budget)
+}
+
+/// Returns the line and column (both starting at 1) where `substring`
+/// starts in `source`, if it is a slice of `source`.
+pub fn source_location(source: &str, substring: &str) -> Option<(usize, usize)> {
+    let start = source.as_ptr() as usize;
+    let position = substring.as_ptr() as usize;
+    if position < start || position > start + source.len() {
+        return None;
+    }
+    let before = &source[0..position-start];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+    Some((line, column))
+}